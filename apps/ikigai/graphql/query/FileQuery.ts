@@ -22,6 +22,19 @@ export const GET_DOWNLOAD_URL_BY_PAGE_CONTENT_ID = gql`
 
 export const GET_AUDIO_WAVEFORM = gql`
   query GetAudioWaveform($fileId: UUID!, $documentId: UUID!) {
-    fileWaveform(fileId: $fileId, documentId: $documentId)
+    fileWaveform(fileId: $fileId, documentId: $documentId) {
+      waveform
+      comments {
+        id
+        startTime
+        endTime
+        comment
+        creator {
+          id
+          firstName
+          lastName
+        }
+      }
+    }
   }
 `;
@@ -423,7 +423,7 @@ export interface RemoveDocumentAssigneeVariables {
 export interface UpsertWritingBlock_documentUpsertWritingBlock {
   id: any;
   pageContentId: any;
-  creatorId: number;
+  creatorId: number | null;
   content: any;
   updatedAt: number;
   createdAt: number;
@@ -450,7 +450,7 @@ export interface UpsertWritingBlockVariables {
 export interface CloneWritingBlock_documentCloneWritingBlock {
   id: any;
   pageContentId: any;
-  creatorId: number;
+  creatorId: number | null;
   content: any;
   updatedAt: number;
   createdAt: number;
@@ -1121,7 +1121,7 @@ export interface GetDocumentAssigneesVariables {
 export interface GetWritingBlock_documentGetWritingBlock {
   id: any;
   pageContentId: any;
-  creatorId: number;
+  creatorId: number | null;
   content: any;
   updatedAt: number;
   createdAt: number;
@@ -1191,8 +1191,27 @@ export interface GetDownloadUrlVariables {
 // GraphQL query operation: GetAudioWaveform
 // ====================================================
 
+export interface GetAudioWaveform_fileWaveform_comments_creator {
+  id: number;
+  firstName: string;
+  lastName: string;
+}
+
+export interface GetAudioWaveform_fileWaveform_comments {
+  id: any;
+  startTime: number;
+  endTime: number | null;
+  comment: string;
+  creator: GetAudioWaveform_fileWaveform_comments_creator;
+}
+
+export interface GetAudioWaveform_fileWaveform {
+  waveform: string | null;
+  comments: GetAudioWaveform_fileWaveform_comments[];
+}
+
 export interface GetAudioWaveform {
-  fileWaveform: string | null;
+  fileWaveform: GetAudioWaveform_fileWaveform;
 }
 
 export interface GetAudioWaveformVariables {
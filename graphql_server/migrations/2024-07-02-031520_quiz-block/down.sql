@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE quiz_answer_keys;
+DROP TABLE quiz_blocks;
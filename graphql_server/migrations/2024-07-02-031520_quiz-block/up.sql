@@ -0,0 +1,20 @@
+-- Your SQL goes here
+CREATE TABLE quiz_blocks (
+    id UUID PRIMARY KEY,
+    page_content_id UUID NOT NULL REFERENCES page_contents(id) ON DELETE CASCADE ,
+    creator_id INT NOT NULL REFERENCES users(id) ON DELETE CASCADE ,
+    quiz_type INT NOT NULL,
+    content JSONB NOT NULL,
+    score FLOAT8 NOT NULL DEFAULT 1,
+    answer JSONB,
+    original_quiz_block_id UUID,
+    updated_at BIGINT DEFAULT (EXTRACT(EPOCH FROM now()))::BIGINT NOT NULL,
+    created_at BIGINT DEFAULT (EXTRACT(EPOCH FROM now()))::BIGINT NOT NULL
+);
+
+CREATE TABLE quiz_answer_keys (
+    quiz_block_id UUID PRIMARY KEY REFERENCES quiz_blocks(id) ON DELETE CASCADE ,
+    answer JSONB NOT NULL,
+    updated_at BIGINT DEFAULT (EXTRACT(EPOCH FROM now()))::BIGINT NOT NULL,
+    created_at BIGINT DEFAULT (EXTRACT(EPOCH FROM now()))::BIGINT NOT NULL
+);
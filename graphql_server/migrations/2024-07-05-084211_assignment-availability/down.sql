@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE assignments
+    DROP COLUMN open_at,
+    DROP COLUMN close_at;
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE assignments
+    ADD COLUMN open_at BIGINT,
+    ADD COLUMN close_at BIGINT;
@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE assignments
+    DROP COLUMN due_at,
+    DROP COLUMN late_policy,
+    DROP COLUMN late_penalty;
+
+ALTER TABLE assignment_submissions
+    DROP COLUMN is_late,
+    DROP COLUMN late_by;
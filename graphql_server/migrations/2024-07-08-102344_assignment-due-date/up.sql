@@ -0,0 +1,9 @@
+-- Your SQL goes here
+ALTER TABLE assignments
+    ADD COLUMN due_at BIGINT,
+    ADD COLUMN late_policy INT NOT NULL DEFAULT 0,
+    ADD COLUMN late_penalty FLOAT8;
+
+ALTER TABLE assignment_submissions
+    ADD COLUMN is_late BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN late_by BIGINT NOT NULL DEFAULT 0;
@@ -0,0 +1,10 @@
+-- Your SQL goes here
+CREATE TABLE assignment_accommodations (
+    assignment_id INT NOT NULL REFERENCES assignments(id) ON DELETE CASCADE ,
+    user_id INT NOT NULL REFERENCES users(id) ON DELETE CASCADE ,
+    test_duration INT,
+    max_number_of_attempt INT,
+    updated_at BIGINT DEFAULT (EXTRACT(EPOCH FROM now()))::BIGINT NOT NULL,
+    created_at BIGINT DEFAULT (EXTRACT(EPOCH FROM now()))::BIGINT NOT NULL,
+    PRIMARY KEY (assignment_id, user_id)
+);
@@ -0,0 +1,10 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE assignments
+    DROP CONSTRAINT assignments_band_score_id_fkey,
+    ADD CONSTRAINT assignments_band_score_id_fkey
+        FOREIGN KEY (band_score_id) REFERENCES band_scores(id);
+
+DELETE FROM band_scores WHERE user_id IS NOT NULL OR space_id IS NOT NULL;
+ALTER TABLE band_scores
+    DROP COLUMN user_id,
+    DROP COLUMN space_id;
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE mock_test_sections;
+DROP TABLE mock_tests;
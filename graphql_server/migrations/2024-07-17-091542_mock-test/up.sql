@@ -0,0 +1,18 @@
+-- Your SQL goes here
+CREATE TABLE mock_tests (
+    id SERIAL PRIMARY KEY,
+    space_id INT NOT NULL REFERENCES spaces(id) ON DELETE CASCADE ,
+    creator_id INT NOT NULL REFERENCES users(id) ,
+    name TEXT NOT NULL,
+    updated_at BIGINT DEFAULT (EXTRACT(EPOCH FROM now()))::BIGINT NOT NULL,
+    created_at BIGINT DEFAULT (EXTRACT(EPOCH FROM now()))::BIGINT NOT NULL
+);
+
+CREATE TABLE mock_test_sections (
+    mock_test_id INT NOT NULL REFERENCES mock_tests(id) ON DELETE CASCADE ,
+    skill INT NOT NULL,
+    document_id UUID NOT NULL REFERENCES documents(id) ON DELETE CASCADE ,
+    updated_at BIGINT DEFAULT (EXTRACT(EPOCH FROM now()))::BIGINT NOT NULL,
+    created_at BIGINT DEFAULT (EXTRACT(EPOCH FROM now()))::BIGINT NOT NULL,
+    PRIMARY KEY (mock_test_id, skill)
+);
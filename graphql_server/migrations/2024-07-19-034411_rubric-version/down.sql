@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE rubric_submissions
+    DROP COLUMN rubric_version;
+DROP TABLE rubric_versions;
+ALTER TABLE rubrics
+    DROP COLUMN version;
@@ -0,0 +1,18 @@
+-- Your SQL goes here
+ALTER TABLE rubrics
+    ADD COLUMN version INT DEFAULT 1 NOT NULL;
+
+CREATE TABLE rubric_versions (
+    rubric_id UUID NOT NULL REFERENCES rubrics(id) ON DELETE CASCADE ,
+    version INT NOT NULL,
+    data JSONB NOT NULL,
+    created_at BIGINT DEFAULT (EXTRACT(EPOCH FROM now()))::BIGINT NOT NULL,
+    PRIMARY KEY (rubric_id, version)
+);
+
+INSERT INTO rubric_versions (rubric_id, version, data)
+SELECT id, version, data FROM rubrics;
+
+-- Existing rubric submissions were copied before versioning, keep them as unknown version
+ALTER TABLE rubric_submissions
+    ADD COLUMN rubric_version INT;
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE rubrics
+    ADD COLUMN space_id INT REFERENCES spaces(id) ON DELETE SET NULL;
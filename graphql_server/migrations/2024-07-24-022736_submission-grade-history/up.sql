@@ -0,0 +1,12 @@
+-- Your SQL goes here
+CREATE TABLE submission_grade_histories (
+    id SERIAL PRIMARY KEY,
+    submission_id INT NOT NULL REFERENCES assignment_submissions(id) ON DELETE CASCADE ,
+    actor_id INT REFERENCES users(id) ON DELETE SET NULL ,
+    source INT NOT NULL,
+    old_final_grade FLOAT8,
+    new_final_grade FLOAT8,
+    old_feedback TEXT,
+    new_feedback TEXT,
+    created_at BIGINT DEFAULT (EXTRACT(EPOCH FROM now()))::BIGINT NOT NULL
+);
@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE assignment_submissions
+    DROP COLUMN grade_held;
+
+ALTER TABLE assignments
+    DROP COLUMN hold_grades,
+    DROP COLUMN release_grades_at,
+    DROP COLUMN grades_released_at;
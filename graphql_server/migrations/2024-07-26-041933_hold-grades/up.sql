@@ -0,0 +1,8 @@
+-- Your SQL goes here
+ALTER TABLE assignments
+    ADD COLUMN hold_grades BOOL DEFAULT FALSE NOT NULL,
+    ADD COLUMN release_grades_at BIGINT,
+    ADD COLUMN grades_released_at BIGINT;
+
+ALTER TABLE assignment_submissions
+    ADD COLUMN grade_held BOOL DEFAULT FALSE NOT NULL;
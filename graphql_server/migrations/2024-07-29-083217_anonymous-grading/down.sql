@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE assignment_submissions
+    DROP COLUMN anonymous_id;
+
+ALTER TABLE assignments
+    DROP COLUMN anonymous_grading;
@@ -0,0 +1,6 @@
+-- Your SQL goes here
+ALTER TABLE assignments
+    ADD COLUMN anonymous_grading BOOL DEFAULT FALSE NOT NULL;
+
+ALTER TABLE assignment_submissions
+    ADD COLUMN anonymous_id VARCHAR(32) DEFAULT substr(md5(random()::text), 1, 12) NOT NULL;
@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE submission_markings;
+
+ALTER TABLE assignments
+    DROP COLUMN double_marking,
+    DROP COLUMN discrepancy_threshold;
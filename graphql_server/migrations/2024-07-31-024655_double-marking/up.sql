@@ -0,0 +1,15 @@
+-- Your SQL goes here
+ALTER TABLE assignments
+    ADD COLUMN double_marking BOOL DEFAULT FALSE NOT NULL,
+    ADD COLUMN discrepancy_threshold FLOAT8 DEFAULT 0 NOT NULL;
+
+CREATE TABLE submission_markings (
+    submission_id INT NOT NULL REFERENCES assignment_submissions(id) ON DELETE CASCADE ,
+    marker_id INT NOT NULL REFERENCES users(id) ON DELETE CASCADE ,
+    grade FLOAT8 NOT NULL,
+    feedback TEXT,
+    graded_data JSONB,
+    updated_at BIGINT DEFAULT (EXTRACT(EPOCH FROM now()))::BIGINT NOT NULL,
+    created_at BIGINT DEFAULT (EXTRACT(EPOCH FROM now()))::BIGINT NOT NULL,
+    PRIMARY KEY (submission_id, marker_id)
+);
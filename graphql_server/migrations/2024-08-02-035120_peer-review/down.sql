@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE peer_reviews;
+
+ALTER TABLE assignments
+    DROP COLUMN peer_review_count,
+    DROP COLUMN peer_review_weight,
+    DROP COLUMN peer_reviews_allocated_at;
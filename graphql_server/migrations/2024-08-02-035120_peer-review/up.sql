@@ -0,0 +1,16 @@
+-- Your SQL goes here
+ALTER TABLE assignments
+    ADD COLUMN peer_review_count INT DEFAULT 0 NOT NULL,
+    ADD COLUMN peer_review_weight FLOAT8 DEFAULT 0 NOT NULL,
+    ADD COLUMN peer_reviews_allocated_at BIGINT;
+
+CREATE TABLE peer_reviews (
+    submission_id INT NOT NULL REFERENCES assignment_submissions(id) ON DELETE CASCADE ,
+    reviewer_id INT NOT NULL REFERENCES users(id) ON DELETE CASCADE ,
+    graded_data JSONB,
+    score FLOAT8,
+    submitted_at BIGINT,
+    updated_at BIGINT DEFAULT (EXTRACT(EPOCH FROM now()))::BIGINT NOT NULL,
+    created_at BIGINT DEFAULT (EXTRACT(EPOCH FROM now()))::BIGINT NOT NULL,
+    PRIMARY KEY (submission_id, reviewer_id)
+);
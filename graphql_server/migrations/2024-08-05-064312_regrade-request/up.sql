@@ -0,0 +1,14 @@
+-- Your SQL goes here
+CREATE TABLE regrade_requests (
+    id SERIAL PRIMARY KEY,
+    submission_id INT NOT NULL REFERENCES assignment_submissions(id) ON DELETE CASCADE ,
+    requester_id INT NOT NULL REFERENCES users(id) ON DELETE CASCADE ,
+    reason TEXT NOT NULL,
+    criterion TEXT,
+    status INT DEFAULT 0 NOT NULL,
+    responder_id INT REFERENCES users(id) ON DELETE SET NULL ,
+    response TEXT,
+    responded_at BIGINT,
+    updated_at BIGINT DEFAULT (EXTRACT(EPOCH FROM now()))::BIGINT NOT NULL,
+    created_at BIGINT DEFAULT (EXTRACT(EPOCH FROM now()))::BIGINT NOT NULL
+);
@@ -0,0 +1,14 @@
+-- Your SQL goes here
+CREATE TABLE feedback_annotations (
+    id UUID PRIMARY KEY,
+    submission_id INT NOT NULL REFERENCES assignment_submissions(id) ON DELETE CASCADE ,
+    page_content_id UUID NOT NULL REFERENCES page_contents(id) ON DELETE CASCADE ,
+    writing_block_id UUID REFERENCES writing_blocks(id) ON DELETE CASCADE ,
+    from_offset INT NOT NULL,
+    to_offset INT NOT NULL,
+    quoted_text TEXT NOT NULL,
+    comment TEXT NOT NULL,
+    creator_id INT NOT NULL REFERENCES users(id) ON DELETE CASCADE ,
+    updated_at BIGINT DEFAULT (EXTRACT(EPOCH FROM now()))::BIGINT NOT NULL,
+    created_at BIGINT DEFAULT (EXTRACT(EPOCH FROM now()))::BIGINT NOT NULL
+);
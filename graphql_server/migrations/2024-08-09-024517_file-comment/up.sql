@@ -0,0 +1,12 @@
+-- Your SQL goes here
+CREATE TABLE file_comments (
+    id UUID PRIMARY KEY,
+    submission_id INT NOT NULL REFERENCES assignment_submissions(id) ON DELETE CASCADE ,
+    file_id UUID NOT NULL REFERENCES files(uuid) ON DELETE CASCADE ,
+    start_time FLOAT8 NOT NULL,
+    end_time FLOAT8,
+    comment TEXT NOT NULL,
+    creator_id INT NOT NULL REFERENCES users(id) ON DELETE CASCADE ,
+    updated_at BIGINT DEFAULT (EXTRACT(EPOCH FROM now()))::BIGINT NOT NULL,
+    created_at BIGINT DEFAULT (EXTRACT(EPOCH FROM now()))::BIGINT NOT NULL
+);
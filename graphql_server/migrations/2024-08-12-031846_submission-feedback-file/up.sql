@@ -0,0 +1,8 @@
+-- Your SQL goes here
+CREATE TABLE submission_feedback_files (
+    submission_id INT NOT NULL REFERENCES assignment_submissions(id) ON DELETE CASCADE ,
+    file_id UUID NOT NULL REFERENCES files(uuid) ON DELETE CASCADE ,
+    creator_id INT NOT NULL REFERENCES users(id) ON DELETE CASCADE ,
+    created_at BIGINT DEFAULT (EXTRACT(EPOCH FROM now()))::BIGINT NOT NULL,
+    PRIMARY KEY (submission_id, file_id)
+);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE band_scores
+    DROP COLUMN preset_key,
+    DROP COLUMN by_percentage;
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE band_scores
+    ADD COLUMN preset_key VARCHAR UNIQUE,
+    ADD COLUMN by_percentage BOOLEAN DEFAULT false NOT NULL;
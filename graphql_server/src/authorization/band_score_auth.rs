@@ -0,0 +1,34 @@
+use oso::PolarClass;
+use strum_macros::{Display, EnumString};
+
+use crate::db::BandScore;
+
+#[derive(Clone, Debug, PolarClass)]
+pub struct BandScoreAuth {
+    #[polar(attribute)]
+    pub id: i32,
+    #[polar(attribute)]
+    pub user_id: i32,
+    #[polar(attribute)]
+    pub space_id: i32,
+    #[polar(attribute)]
+    pub is_preset: bool,
+}
+
+impl BandScoreAuth {
+    pub fn new(band_score: &BandScore) -> Self {
+        Self {
+            id: band_score.id,
+            user_id: band_score.user_id.unwrap_or(-1),
+            space_id: band_score.space_id.unwrap_or(-1),
+            is_preset: band_score.is_preset(),
+        }
+    }
+}
+
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq, EnumString, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum BandScoreActionPermission {
+    ViewBandScore,
+    ManageBandScore,
+}
@@ -26,12 +26,15 @@ pub struct DocumentAuth {
     pub is_private: bool,
     #[polar(attribute)]
     pub is_structured_submission: bool,
+    #[polar(attribute)]
+    pub peer_reviewer_ids: Vec<i32>,
 }
 
 impl DocumentAuth {
     pub fn try_new(conn: &mut PgConnection, document_id: Uuid) -> Result<Self, IkigaiError> {
         let mut allow_for_student_view_answer = false;
         let mut is_doing_submission = false;
+        let mut peer_reviewer_ids = vec![];
 
         let document = Document::find_by_id(conn, document_id)?;
         let submission = Submission::find_by_document(conn, document_id)?;
@@ -40,6 +43,8 @@ impl DocumentAuth {
         if let Some(submission) = &submission {
             allow_for_student_view_answer = submission.allow_for_student_view_answer;
             is_doing_submission = submission.submit_at.is_none();
+            peer_reviewer_ids =
+                PeerReview::find_all_reviewer_ids_by_submission(conn, submission.id)?;
         }
 
         Ok(Self {
@@ -53,6 +58,7 @@ impl DocumentAuth {
             is_private: document.is_private || document.is_default_folder_private,
             // WARN: change by submission attribute to support open assignment.
             is_structured_submission: true,
+            peer_reviewer_ids,
         })
     }
 }
@@ -66,4 +72,5 @@ pub enum DocumentActionPermission {
     EditDocument,
     ManageDocument,
     ViewPageContent,
+    PeerReviewDocument,
 }
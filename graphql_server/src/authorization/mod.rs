@@ -1,8 +1,10 @@
+pub mod band_score_auth;
 pub mod document_auth;
 pub mod rubric_auth;
 pub mod space_auth;
 pub mod user_auth;
 
+pub use band_score_auth::*;
 pub use document_auth::*;
 pub use rubric_auth::*;
 pub use space_auth::*;
@@ -30,6 +32,9 @@ pub fn init_oso() -> Oso {
     let document_builder: ClassBuilder<RubricAuth> = RubricAuth::get_polar_class_builder();
     oso.register_class(document_builder.build()).unwrap();
 
+    let band_score_builder: ClassBuilder<BandScoreAuth> = BandScoreAuth::get_polar_class_builder();
+    oso.register_class(band_score_builder.build()).unwrap();
+
     oso.load_files(vec!["authorization.polar"]).unwrap();
 
     oso
@@ -10,6 +10,8 @@ pub struct RubricAuth {
     pub id: Uuid,
     #[polar(attribute)]
     pub user_id: i32,
+    #[polar(attribute)]
+    pub space_id: i32,
 }
 
 impl RubricAuth {
@@ -17,12 +19,16 @@ impl RubricAuth {
         Self {
             id: rubric.id,
             user_id: rubric.user_id,
+            space_id: rubric.space_id.unwrap_or(-1),
         }
     }
 }
 
 #[derive(Enum, Debug, Copy, Clone, Eq, PartialEq, EnumString, Display)]
 #[strum(serialize_all = "snake_case")]
+#[allow(clippy::enum_variant_names)]
 pub enum RubricActionPermission {
+    ViewRubric,
+    UseRubric,
     ManageRubric,
 }
@@ -0,0 +1,90 @@
+use aj::async_trait::async_trait;
+use aj::Executable;
+
+use crate::connection_pool::get_conn_from_actor;
+use crate::db::{Assignment, Submission};
+use crate::error::IkigaiError;
+use crate::helper::{allocate_peer_reviews, release_grades, submit_submission};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloseAssignment {
+    pub assignment_id: i32,
+    pub close_at: i64,
+}
+
+async fn handle_close_assignment(msg: &CloseAssignment) -> Result<(), IkigaiError> {
+    info!("Start close assignment by background_job job {:?}", msg);
+    let mut conn = get_conn_from_actor().await?;
+    let assignment = Assignment::find_by_id(&mut conn, msg.assignment_id)?;
+
+    // Teacher changed close time, the newer job will handle it
+    if assignment.close_at != Some(msg.close_at) {
+        return Ok(());
+    }
+
+    let submissions = Submission::find_all_in_doing_by_assignment(&mut conn, assignment.id)?;
+    for submission in submissions {
+        submit_submission(&mut conn, &submission, &assignment, true)?;
+    }
+
+    if assignment.peer_review_count > 0 && assignment.peer_reviews_allocated_at.is_none() {
+        // Too few submissions is not a failure of closing
+        if let Err(e) = allocate_peer_reviews(&mut conn, &assignment) {
+            warn!(
+                "Cannot allocate peer reviews of assignment {} by {:?}",
+                assignment.id, e
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[async_trait]
+impl Executable for CloseAssignment {
+    type Output = ();
+
+    async fn execute(&self) {
+        if let Err(e) = handle_close_assignment(self).await {
+            error!(
+                "Cannot close assignment {} in background_job job by {:?}",
+                self.assignment_id, e
+            );
+        };
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseGrades {
+    pub assignment_id: i32,
+    pub release_at: i64,
+}
+
+async fn handle_release_grades(msg: &ReleaseGrades) -> Result<(), IkigaiError> {
+    info!("Start release grades by background_job job {:?}", msg);
+    let mut conn = get_conn_from_actor().await?;
+    let assignment = Assignment::find_by_id(&mut conn, msg.assignment_id)?;
+
+    // Teacher rescheduled the release, the newer job will handle it
+    if assignment.release_grades_at != Some(msg.release_at) {
+        return Ok(());
+    }
+
+    release_grades(&mut conn, assignment.id)?;
+
+    Ok(())
+}
+
+#[async_trait]
+impl Executable for ReleaseGrades {
+    type Output = ();
+
+    async fn execute(&self) {
+        if let Err(e) = handle_release_grades(self).await {
+            error!(
+                "Cannot release grades of assignment {} in background_job job by {:?}",
+                self.assignment_id, e
+            );
+        };
+    }
+}
@@ -1,8 +1,10 @@
+pub mod assignment_job;
 pub mod storage_job;
 pub mod submission_job;
 
 use aj::AJ;
 
+use crate::background_job::assignment_job::{CloseAssignment, ReleaseGrades};
 use crate::background_job::storage_job::GenerateWaveform;
 use crate::background_job::submission_job::CompleteSubmission;
 
@@ -10,5 +12,7 @@ pub fn register_jobs() {
     let url = std::env::var("REDIS_URL").unwrap();
     let redis = aj::redis::Redis::new(url);
     AJ::register::<CompleteSubmission>("complete_submission", redis.clone());
+    AJ::register::<CloseAssignment>("close_assignment", redis.clone());
+    AJ::register::<ReleaseGrades>("release_grades", redis.clone());
     AJ::register::<GenerateWaveform>("generate_waveform", redis);
 }
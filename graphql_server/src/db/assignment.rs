@@ -3,7 +3,8 @@ use diesel::sql_types::Integer;
 use diesel::{AsChangeset, ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
 use uuid::Uuid;
 
-use super::schema::assignments;
+use super::schema::{assignment_accommodations, assignments};
+use super::Submission;
 use crate::impl_enum_for_db;
 use crate::util::get_now_as_secs;
 
@@ -25,6 +26,46 @@ impl Default for GradeMethod {
     }
 }
 
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, FromPrimitive, ToPrimitive, AsExpression, FromSqlRow, Enum,
+)]
+#[diesel(sql_type = Integer)]
+pub enum LatePolicy {
+    None,
+    // Late penalty is percentage of grade deducted per started late day
+    PercentagePerDay,
+    // Late penalty is the maximum grade of late submission
+    Cap,
+}
+
+impl_enum_for_db!(LatePolicy);
+
+impl Default for LatePolicy {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+// Which attempt counts when student has many submissions
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, FromPrimitive, ToPrimitive, AsExpression, FromSqlRow, Enum,
+)]
+#[diesel(sql_type = Integer)]
+pub enum AttemptScoringPolicy {
+    Latest,
+    Highest,
+    First,
+    Average,
+}
+
+impl_enum_for_db!(AttemptScoringPolicy);
+
+impl Default for AttemptScoringPolicy {
+    fn default() -> Self {
+        Self::Latest
+    }
+}
+
 #[derive(Debug, Clone, Insertable, Default)]
 #[diesel(table_name = assignments)]
 pub struct NewAssignment {
@@ -37,6 +78,18 @@ pub struct NewAssignment {
     pub band_score_id: Option<i32>,
     pub grade_method: GradeMethod,
     pub grade_by_rubric_id: Option<Uuid>,
+    pub open_at: Option<i64>,
+    pub close_at: Option<i64>,
+    pub due_at: Option<i64>,
+    pub late_policy: LatePolicy,
+    pub late_penalty: Option<f64>,
+    pub attempt_scoring_policy: AttemptScoringPolicy,
+    pub hold_grades: bool,
+    pub anonymous_grading: bool,
+    pub double_marking: bool,
+    pub discrepancy_threshold: f64,
+    pub peer_review_count: i32,
+    pub peer_review_weight: f64,
 }
 
 impl From<Assignment> for NewAssignment {
@@ -51,6 +104,18 @@ impl From<Assignment> for NewAssignment {
             band_score_id: assignment.band_score_id,
             grade_method: assignment.grade_method,
             grade_by_rubric_id: assignment.grade_by_rubric_id,
+            open_at: assignment.open_at,
+            close_at: assignment.close_at,
+            due_at: assignment.due_at,
+            late_policy: assignment.late_policy,
+            late_penalty: assignment.late_penalty,
+            attempt_scoring_policy: assignment.attempt_scoring_policy,
+            hold_grades: assignment.hold_grades,
+            anonymous_grading: assignment.anonymous_grading,
+            double_marking: assignment.double_marking,
+            discrepancy_threshold: assignment.discrepancy_threshold,
+            peer_review_count: assignment.peer_review_count,
+            peer_review_weight: assignment.peer_review_weight,
         }
     }
 }
@@ -78,6 +143,32 @@ pub struct UpdateAssignmentData {
     pub band_score_id: Option<i32>,
     pub grade_method: GradeMethod,
     pub grade_by_rubric_id: Option<Uuid>,
+    pub open_at: Option<i64>,
+    pub close_at: Option<i64>,
+    pub due_at: Option<i64>,
+    #[graphql(default)]
+    pub late_policy: LatePolicy,
+    pub late_penalty: Option<f64>,
+    #[graphql(default)]
+    pub attempt_scoring_policy: AttemptScoringPolicy,
+    // Keep grades hidden from students until teacher releases them for the first time
+    #[graphql(default)]
+    pub hold_grades: bool,
+    // Hide identity of students from graders until grades are released
+    #[graphql(default)]
+    pub anonymous_grading: bool,
+    // Submissions are marked independently by several teachers, then moderated
+    #[graphql(default)]
+    pub double_marking: bool,
+    // Markings differing more than this are flagged for moderation
+    #[graphql(default)]
+    pub discrepancy_threshold: f64,
+    // Number of peer reviewers of each submission, 0 means no peer review
+    #[graphql(default)]
+    pub peer_review_count: i32,
+    // Portion of final grade taken from average peer review score, from 0 to 1
+    #[graphql(default)]
+    pub peer_review_weight: f64,
     #[graphql(skip)]
     pub updated_at: i64,
 }
@@ -95,9 +186,111 @@ pub struct Assignment {
     pub band_score_id: Option<i32>,
     pub grade_method: GradeMethod,
     pub grade_by_rubric_id: Option<Uuid>,
+    pub open_at: Option<i64>,
+    pub close_at: Option<i64>,
+    pub due_at: Option<i64>,
+    pub late_policy: LatePolicy,
+    pub late_penalty: Option<f64>,
+    pub attempt_scoring_policy: AttemptScoringPolicy,
+    pub hold_grades: bool,
+    // Scheduled time of the latest grade release
+    pub release_grades_at: Option<i64>,
+    // Grades given after the first release are not held anymore
+    pub grades_released_at: Option<i64>,
+    pub anonymous_grading: bool,
+    pub double_marking: bool,
+    pub discrepancy_threshold: f64,
+    pub peer_review_count: i32,
+    pub peer_review_weight: f64,
+    pub peer_reviews_allocated_at: Option<i64>,
 }
 
 impl Assignment {
+    pub fn test_duration_for(
+        &self,
+        accommodation: Option<&AssignmentAccommodation>,
+    ) -> Option<i32> {
+        accommodation
+            .and_then(|accommodation| accommodation.test_duration)
+            .or(self.test_duration)
+    }
+
+    pub fn max_number_of_attempt_for(
+        &self,
+        accommodation: Option<&AssignmentAccommodation>,
+    ) -> Option<i32> {
+        accommodation
+            .and_then(|accommodation| accommodation.max_number_of_attempt)
+            .or(self.max_number_of_attempt)
+    }
+
+    // Only submitted attempts with final grade are counted
+    pub fn calculate_effective_grade(&self, submissions: &[Submission]) -> Option<f64> {
+        let attempts: Vec<(i32, f64)> = submissions
+            .iter()
+            .filter(|submission| submission.submit_at.is_some())
+            .filter_map(|submission| {
+                submission
+                    .final_grade
+                    .map(|grade| (submission.attempt_number, grade))
+            })
+            .collect();
+        if attempts.is_empty() {
+            return None;
+        }
+
+        match self.attempt_scoring_policy {
+            AttemptScoringPolicy::Latest => attempts
+                .iter()
+                .max_by_key(|(attempt_number, _)| *attempt_number)
+                .map(|(_, grade)| *grade),
+            AttemptScoringPolicy::First => attempts
+                .iter()
+                .min_by_key(|(attempt_number, _)| *attempt_number)
+                .map(|(_, grade)| *grade),
+            AttemptScoringPolicy::Highest => {
+                attempts.iter().map(|(_, grade)| *grade).reduce(f64::max)
+            }
+            AttemptScoringPolicy::Average => {
+                Some(attempts.iter().map(|(_, grade)| grade).sum::<f64>() / attempts.len() as f64)
+            }
+        }
+    }
+
+    pub fn apply_late_penalty(&self, grade: f64, late_by: i64) -> f64 {
+        if late_by <= 0 {
+            return grade;
+        }
+
+        // Policy without penalty does not change grade
+        let late_penalty = match self.late_penalty {
+            Some(late_penalty) => late_penalty,
+            None => return grade,
+        };
+        match self.late_policy {
+            LatePolicy::None => grade,
+            LatePolicy::PercentagePerDay => {
+                let late_days = (late_by as f64 / 86400.0).ceil();
+                let deduction = (late_penalty * late_days / 100.0).clamp(0.0, 1.0);
+                grade * (1.0 - deduction)
+            }
+            LatePolicy::Cap => grade.min(late_penalty),
+        }
+    }
+
+    pub fn apply_peer_review_score(&self, grade: f64, peer_review_score: Option<f64>) -> f64 {
+        if let Some(peer_review_score) = peer_review_score {
+            let weight = self.peer_review_weight.clamp(0.0, 1.0);
+            return grade * (1.0 - weight) + peer_review_score * weight;
+        }
+
+        grade
+    }
+
+    pub fn is_holding_grades(&self) -> bool {
+        self.hold_grades && self.grades_released_at.is_none()
+    }
+
     pub fn insert(
         conn: &mut PgConnection,
         mut new_assignment: NewAssignment,
@@ -128,6 +321,45 @@ impl Assignment {
             .get_result(conn)
     }
 
+    pub fn update_release_grades_at(
+        conn: &mut PgConnection,
+        assignment_id: i32,
+        release_grades_at: Option<i64>,
+    ) -> Result<Self, Error> {
+        diesel::update(assignments::table.find(assignment_id))
+            .set((
+                assignments::release_grades_at.eq(release_grades_at),
+                assignments::updated_at.eq(get_now_as_secs()),
+            ))
+            .get_result(conn)
+    }
+
+    pub fn mark_grades_released(
+        conn: &mut PgConnection,
+        assignment_id: i32,
+    ) -> Result<Self, Error> {
+        diesel::update(assignments::table.find(assignment_id))
+            .set((
+                assignments::release_grades_at.eq(None::<i64>),
+                assignments::grades_released_at.eq(get_now_as_secs()),
+                assignments::updated_at.eq(get_now_as_secs()),
+            ))
+            .get_result(conn)
+    }
+
+    pub fn update_peer_reviews_allocated_at(
+        conn: &mut PgConnection,
+        assignment_id: i32,
+        peer_reviews_allocated_at: Option<i64>,
+    ) -> Result<Self, Error> {
+        diesel::update(assignments::table.find(assignment_id))
+            .set((
+                assignments::peer_reviews_allocated_at.eq(peer_reviews_allocated_at),
+                assignments::updated_at.eq(get_now_as_secs()),
+            ))
+            .get_result(conn)
+    }
+
     pub fn find_by_id(conn: &mut PgConnection, assignment_id: i32) -> Result<Self, Error> {
         assignments::table.find(assignment_id).first(conn)
     }
@@ -164,3 +396,68 @@ impl Assignment {
             .get_results(conn)
     }
 }
+
+// Per student override of assignment settings, None means using assignment setting
+#[derive(Debug, Clone, Insertable, Queryable, SimpleObject, InputObject)]
+#[diesel(table_name = assignment_accommodations)]
+#[graphql(complex, input_name = "AssignmentAccommodationInput")]
+pub struct AssignmentAccommodation {
+    pub assignment_id: i32,
+    pub user_id: i32,
+    pub test_duration: Option<i32>,
+    pub max_number_of_attempt: Option<i32>,
+    #[graphql(skip_input)]
+    pub updated_at: i64,
+    #[graphql(skip_input)]
+    pub created_at: i64,
+}
+
+impl AssignmentAccommodation {
+    pub fn upsert(conn: &mut PgConnection, mut item: Self) -> Result<Self, Error> {
+        item.updated_at = get_now_as_secs();
+        item.created_at = get_now_as_secs();
+        diesel::insert_into(assignment_accommodations::table)
+            .values(&item)
+            .on_conflict((
+                assignment_accommodations::assignment_id,
+                assignment_accommodations::user_id,
+            ))
+            .do_update()
+            .set((
+                assignment_accommodations::test_duration.eq(item.test_duration),
+                assignment_accommodations::max_number_of_attempt.eq(item.max_number_of_attempt),
+                assignment_accommodations::updated_at.eq(item.updated_at),
+            ))
+            .get_result(conn)
+    }
+
+    pub fn find_opt(
+        conn: &mut PgConnection,
+        assignment_id: i32,
+        user_id: i32,
+    ) -> Result<Option<Self>, Error> {
+        match assignment_accommodations::table
+            .find((assignment_id, user_id))
+            .first(conn)
+        {
+            Ok(item) => Ok(Some(item)),
+            Err(Error::NotFound) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn find_all_by_assignment(
+        conn: &mut PgConnection,
+        assignment_id: i32,
+    ) -> Result<Vec<Self>, Error> {
+        assignment_accommodations::table
+            .filter(assignment_accommodations::assignment_id.eq(assignment_id))
+            .get_results(conn)
+    }
+
+    pub fn remove(conn: &mut PgConnection, assignment_id: i32, user_id: i32) -> Result<(), Error> {
+        diesel::delete(assignment_accommodations::table.find((assignment_id, user_id)))
+            .execute(conn)?;
+        Ok(())
+    }
+}
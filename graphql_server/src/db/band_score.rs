@@ -1,9 +1,10 @@
 use diesel::result::Error;
 use diesel::sql_types::Jsonb;
-use diesel::{PgConnection, QueryDsl, RunQueryDsl};
+use diesel::{BoolExpressionMethods, ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
 
 use super::schema::band_scores;
 use crate::impl_jsonb_for_db;
+use crate::util::get_now_as_secs;
 
 #[derive(Debug, Clone, Serialize, Deserialize, SimpleObject, InputObject)]
 #[graphql(input_name = "BandScoreRangeInput")]
@@ -91,20 +92,185 @@ impl BandScoreRanges {
         ];
         Self { items }
     }
+
+    pub fn init_toeic_listening() -> Self {
+        let items = vec![
+            BandScoreRange::new(0, 10, 5.0),
+            BandScoreRange::new(11, 20, 60.0),
+            BandScoreRange::new(21, 30, 110.0),
+            BandScoreRange::new(31, 40, 160.0),
+            BandScoreRange::new(41, 50, 215.0),
+            BandScoreRange::new(51, 60, 270.0),
+            BandScoreRange::new(61, 70, 325.0),
+            BandScoreRange::new(71, 80, 380.0),
+            BandScoreRange::new(81, 90, 435.0),
+            BandScoreRange::new(91, 100, 495.0),
+        ];
+        Self { items }
+    }
+
+    pub fn init_toeic_reading() -> Self {
+        let items = vec![
+            BandScoreRange::new(0, 10, 5.0),
+            BandScoreRange::new(11, 20, 45.0),
+            BandScoreRange::new(21, 30, 95.0),
+            BandScoreRange::new(31, 40, 145.0),
+            BandScoreRange::new(41, 50, 200.0),
+            BandScoreRange::new(51, 60, 255.0),
+            BandScoreRange::new(61, 70, 310.0),
+            BandScoreRange::new(71, 80, 365.0),
+            BandScoreRange::new(81, 90, 420.0),
+            BandScoreRange::new(91, 100, 495.0),
+        ];
+        Self { items }
+    }
+
+    // Percentage to CEFR level, from A1 = 1 to C2 = 6
+    pub fn init_cefr() -> Self {
+        let items = vec![
+            BandScoreRange::new(0, 19, 1.0),
+            BandScoreRange::new(20, 39, 2.0),
+            BandScoreRange::new(40, 59, 3.0),
+            BandScoreRange::new(60, 74, 4.0),
+            BandScoreRange::new(75, 89, 5.0),
+            BandScoreRange::new(90, 100, 6.0),
+        ];
+        Self { items }
+    }
+
+    // Percentage to Cambridge English Scale
+    pub fn init_cambridge_scale() -> Self {
+        let items = vec![
+            BandScoreRange::new(0, 19, 100.0),
+            BandScoreRange::new(20, 39, 120.0),
+            BandScoreRange::new(40, 59, 140.0),
+            BandScoreRange::new(60, 74, 160.0),
+            BandScoreRange::new(75, 79, 170.0),
+            BandScoreRange::new(80, 89, 180.0),
+            BandScoreRange::new(90, 100, 190.0),
+        ];
+        Self { items }
+    }
+
+    // Ranges must be continuous, so every grade belongs to exactly one range
+    pub fn check(&self) -> Result<(), String> {
+        if self.items.is_empty() {
+            return Err("Band score needs at least one range".into());
+        }
+
+        let mut items = self.items.clone();
+        items.sort_by_key(|item| item.from);
+        for item in &items {
+            if item.from > item.to {
+                return Err(format!(
+                    "Range {} - {} has start greater than end",
+                    item.from, item.to
+                ));
+            }
+        }
+        for pair in items.windows(2) {
+            let (prev, next) = (&pair[0], &pair[1]);
+            if next.from <= prev.to {
+                return Err(format!(
+                    "Range {} - {} overlaps with range {} - {}",
+                    prev.from, prev.to, next.from, next.to
+                ));
+            }
+            if next.from > prev.to + 1 {
+                return Err(format!(
+                    "There is a gap between range {} - {} and range {} - {}",
+                    prev.from, prev.to, next.from, next.to
+                ));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl_jsonb_for_db!(BandScoreRanges);
 
+// IELTS rounds to the nearest half band: x.25 goes up to x.5 and x.75 goes up to x + 1
+pub fn round_ielts_band(score: f64) -> f64 {
+    (score * 2.0 + 0.5).floor() / 2.0
+}
+
 #[derive(Debug, Clone, Insertable, InputObject)]
 #[diesel(table_name = band_scores)]
 pub struct NewBandScore {
     pub name: String,
     pub range: BandScoreRanges,
+    #[graphql(skip)]
+    pub user_id: Option<i32>,
+    // Share with teachers of space
+    pub space_id: Option<i32>,
+    // Stable identifier of preset, name of preset can be changed
+    #[graphql(skip)]
+    pub preset_key: Option<String>,
+    // Ranges are percentage of total score instead of raw score
+    #[graphql(default)]
+    pub by_percentage: bool,
 }
 
 impl NewBandScore {
     pub fn new(name: String, range: BandScoreRanges) -> Self {
-        Self { name, range }
+        Self {
+            name,
+            range,
+            user_id: None,
+            space_id: None,
+            preset_key: None,
+            by_percentage: false,
+        }
+    }
+
+    pub fn preset(key: &str, name: &str, range: BandScoreRanges, by_percentage: bool) -> Self {
+        let mut preset = Self::new(name.into(), range);
+        preset.preset_key = Some(key.into());
+        preset.by_percentage = by_percentage;
+        preset
+    }
+
+    pub fn presets() -> Vec<Self> {
+        vec![
+            Self::preset(
+                "ielts_listening",
+                "IELTS Listening",
+                BandScoreRanges::init_ielts_listening(),
+                false,
+            ),
+            Self::preset(
+                "ielts_reading_academic",
+                "IELTS Reading (Academic)",
+                BandScoreRanges::init_ielts_reading_academic(),
+                false,
+            ),
+            Self::preset(
+                "ielts_reading_general",
+                "IELTS Reading (General Training)",
+                BandScoreRanges::init_ielts_reading_general(),
+                false,
+            ),
+            Self::preset(
+                "toeic_listening",
+                "TOEIC Listening",
+                BandScoreRanges::init_toeic_listening(),
+                false,
+            ),
+            Self::preset(
+                "toeic_reading",
+                "TOEIC Reading",
+                BandScoreRanges::init_toeic_reading(),
+                false,
+            ),
+            Self::preset("cefr", "CEFR Level", BandScoreRanges::init_cefr(), true),
+            Self::preset(
+                "cambridge_scale",
+                "Cambridge English Scale",
+                BandScoreRanges::init_cambridge_scale(),
+                true,
+            ),
+        ]
     }
 }
 
@@ -115,10 +281,25 @@ pub struct BandScore {
     pub range: BandScoreRanges,
     pub updated_at: i64,
     pub created_at: i64,
+    pub user_id: Option<i32>,
+    pub space_id: Option<i32>,
+    #[graphql(skip)]
+    pub preset_key: Option<String>,
+    pub by_percentage: bool,
 }
 
 impl BandScore {
-    pub fn find_score(&self, grade: f64) -> f64 {
+    pub fn is_preset(&self) -> bool {
+        self.user_id.is_none() && self.space_id.is_none()
+    }
+
+    // Grade is converted to percentage of max score if ranges are percentage
+    pub fn find_score(&self, grade: f64, max_grade: f64) -> f64 {
+        let grade = if self.by_percentage && max_grade > 0.0 {
+            (grade / max_grade * 100.0).floor()
+        } else {
+            grade
+        };
         let item = self
             .range
             .items
@@ -127,11 +308,76 @@ impl BandScore {
         item.map_or(grade, |range| range.score)
     }
 
+    pub fn insert(conn: &mut PgConnection, item: NewBandScore) -> Result<Self, Error> {
+        diesel::insert_into(band_scores::table)
+            .values(item)
+            .get_result(conn)
+    }
+
+    pub fn update(
+        conn: &mut PgConnection,
+        band_score_id: i32,
+        item: NewBandScore,
+    ) -> Result<Self, Error> {
+        diesel::update(band_scores::table.find(band_score_id))
+            .set((
+                band_scores::name.eq(item.name),
+                band_scores::range.eq(item.range),
+                band_scores::space_id.eq(item.space_id),
+                band_scores::by_percentage.eq(item.by_percentage),
+                band_scores::updated_at.eq(get_now_as_secs()),
+            ))
+            .get_result(conn)
+    }
+
+    pub fn remove(conn: &mut PgConnection, band_score_id: i32) -> Result<(), Error> {
+        diesel::delete(band_scores::table.find(band_score_id)).execute(conn)?;
+        Ok(())
+    }
+
     pub fn find(conn: &mut PgConnection, band_score_id: i32) -> Result<Self, Error> {
         band_scores::table.find(band_score_id).first(conn)
     }
 
-    pub fn find_all(conn: &mut PgConnection) -> Result<Vec<Self>, Error> {
-        band_scores::table.get_results(conn)
+    pub fn update_preset(
+        conn: &mut PgConnection,
+        band_score_id: i32,
+        preset_key: &str,
+        by_percentage: bool,
+    ) -> Result<Self, Error> {
+        diesel::update(band_scores::table.find(band_score_id))
+            .set((
+                band_scores::preset_key.eq(preset_key),
+                band_scores::by_percentage.eq(by_percentage),
+                band_scores::updated_at.eq(get_now_as_secs()),
+            ))
+            .get_result(conn)
+    }
+
+    pub fn find_all_presets(conn: &mut PgConnection) -> Result<Vec<Self>, Error> {
+        band_scores::table
+            .filter(band_scores::user_id.is_null())
+            .filter(band_scores::space_id.is_null())
+            .order(band_scores::id.asc())
+            .get_results(conn)
+    }
+
+    // Presets, band scores created by user and band scores shared in space
+    pub fn find_all_available(
+        conn: &mut PgConnection,
+        user_id: i32,
+        space_id: Option<i32>,
+    ) -> Result<Vec<Self>, Error> {
+        let is_preset = band_scores::user_id
+            .is_null()
+            .and(band_scores::space_id.is_null());
+        band_scores::table
+            .filter(
+                is_preset
+                    .or(band_scores::user_id.eq(user_id))
+                    .or(band_scores::space_id.eq(space_id)),
+            )
+            .order(band_scores::id.asc())
+            .get_results(conn)
     }
 }
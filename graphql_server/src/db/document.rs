@@ -63,11 +63,12 @@ pub struct UpdatePositionData {
 pub struct Document {
     #[graphql(skip_input)]
     pub id: Uuid,
-    #[graphql(skip_input)]
+    #[graphql(skip)]
     pub creator_id: i32,
     pub parent_id: Option<Uuid>,
     pub cover_photo_id: Option<Uuid>,
     pub index: i32,
+    #[graphql(skip_output)]
     pub title: String,
     #[graphql(skip_input)]
     pub deleted_at: Option<i64>,
@@ -0,0 +1,88 @@
+use diesel::result::Error;
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
+use uuid::Uuid;
+
+use super::schema::feedback_annotations;
+use crate::util::get_now_as_secs;
+
+// Comment of grader on a text range of a writing block, or of page content if writing block is None
+#[derive(Debug, Clone, Insertable, Queryable, SimpleObject, InputObject)]
+#[diesel(table_name = feedback_annotations)]
+#[graphql(complex, input_name = "FeedbackAnnotationInput")]
+pub struct FeedbackAnnotation {
+    #[graphql(skip_input)]
+    pub id: Uuid,
+    pub submission_id: i32,
+    pub page_content_id: Uuid,
+    pub writing_block_id: Option<Uuid>,
+    // Character offsets of annotated text within the block, end exclusive
+    pub from_offset: i32,
+    pub to_offset: i32,
+    // Annotated text when comment is written, to re-anchor if content is changed
+    pub quoted_text: String,
+    pub comment: String,
+    #[graphql(skip_input)]
+    pub creator_id: i32,
+    #[graphql(skip_input)]
+    pub updated_at: i64,
+    #[graphql(skip_input)]
+    pub created_at: i64,
+}
+
+impl FeedbackAnnotation {
+    pub fn insert(conn: &mut PgConnection, mut item: Self) -> Result<Self, Error> {
+        item.updated_at = get_now_as_secs();
+        item.created_at = get_now_as_secs();
+        diesel::insert_into(feedback_annotations::table)
+            .values(item)
+            .get_result(conn)
+    }
+
+    pub fn insert_many(conn: &mut PgConnection, items: Vec<Self>) -> Result<usize, Error> {
+        diesel::insert_into(feedback_annotations::table)
+            .values(items)
+            .execute(conn)
+    }
+
+    pub fn update_comment(
+        conn: &mut PgConnection,
+        id: Uuid,
+        comment: String,
+    ) -> Result<Self, Error> {
+        diesel::update(feedback_annotations::table.find(id))
+            .set((
+                feedback_annotations::comment.eq(comment),
+                feedback_annotations::updated_at.eq(get_now_as_secs()),
+            ))
+            .get_result(conn)
+    }
+
+    pub fn remove(conn: &mut PgConnection, id: Uuid) -> Result<usize, Error> {
+        diesel::delete(feedback_annotations::table.find(id)).execute(conn)
+    }
+
+    pub fn remove_all_by_submission(
+        conn: &mut PgConnection,
+        submission_id: i32,
+    ) -> Result<usize, Error> {
+        diesel::delete(
+            feedback_annotations::table
+                .filter(feedback_annotations::submission_id.eq(submission_id)),
+        )
+        .execute(conn)
+    }
+
+    pub fn find_by_id(conn: &mut PgConnection, id: Uuid) -> Result<Self, Error> {
+        feedback_annotations::table.find(id).first(conn)
+    }
+
+    pub fn find_all_by_submission(
+        conn: &mut PgConnection,
+        submission_id: i32,
+    ) -> Result<Vec<Self>, Error> {
+        feedback_annotations::table
+            .filter(feedback_annotations::submission_id.eq(submission_id))
+            .order(feedback_annotations::created_at.asc())
+            .get_results(conn)
+    }
+}
@@ -0,0 +1,70 @@
+use diesel::result::Error;
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
+use uuid::Uuid;
+
+use super::schema::file_comments;
+use crate::util::get_now_as_secs;
+
+// Comment of grader at a time range of an audio file in submission
+#[derive(Debug, Clone, Insertable, Queryable, SimpleObject, InputObject)]
+#[diesel(table_name = file_comments)]
+#[graphql(complex, input_name = "FileCommentInput")]
+pub struct FileComment {
+    #[graphql(skip_input)]
+    pub id: Uuid,
+    pub submission_id: i32,
+    pub file_id: Uuid,
+    // Seconds from start of audio, None end time means a single point
+    pub start_time: f64,
+    pub end_time: Option<f64>,
+    pub comment: String,
+    #[graphql(skip_input)]
+    pub creator_id: i32,
+    #[graphql(skip_input)]
+    pub updated_at: i64,
+    #[graphql(skip_input)]
+    pub created_at: i64,
+}
+
+impl FileComment {
+    pub fn insert(conn: &mut PgConnection, mut item: Self) -> Result<Self, Error> {
+        item.updated_at = get_now_as_secs();
+        item.created_at = get_now_as_secs();
+        diesel::insert_into(file_comments::table)
+            .values(item)
+            .get_result(conn)
+    }
+
+    pub fn update_comment(
+        conn: &mut PgConnection,
+        id: Uuid,
+        comment: String,
+    ) -> Result<Self, Error> {
+        diesel::update(file_comments::table.find(id))
+            .set((
+                file_comments::comment.eq(comment),
+                file_comments::updated_at.eq(get_now_as_secs()),
+            ))
+            .get_result(conn)
+    }
+
+    pub fn remove(conn: &mut PgConnection, id: Uuid) -> Result<usize, Error> {
+        diesel::delete(file_comments::table.find(id)).execute(conn)
+    }
+
+    pub fn find_by_id(conn: &mut PgConnection, id: Uuid) -> Result<Self, Error> {
+        file_comments::table.find(id).first(conn)
+    }
+
+    pub fn find_all_by_submission_and_file(
+        conn: &mut PgConnection,
+        submission_id: i32,
+        file_id: Uuid,
+    ) -> Result<Vec<Self>, Error> {
+        file_comments::table
+            .filter(file_comments::submission_id.eq(submission_id))
+            .filter(file_comments::file_id.eq(file_id))
+            .order(file_comments::start_time.asc())
+            .get_results(conn)
+    }
+}
@@ -0,0 +1,157 @@
+use diesel::result::Error;
+use diesel::sql_types::Integer;
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
+use uuid::Uuid;
+
+use super::schema::{mock_test_sections, mock_tests};
+use crate::impl_enum_for_db;
+use crate::util::get_now_as_secs;
+
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, FromPrimitive, ToPrimitive, AsExpression, FromSqlRow, Enum,
+)]
+#[diesel(sql_type = Integer)]
+pub enum MockTestSkill {
+    Listening,
+    Reading,
+    Writing,
+    Speaking,
+}
+
+impl_enum_for_db!(MockTestSkill);
+
+impl MockTestSkill {
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::Listening,
+            Self::Reading,
+            Self::Writing,
+            Self::Speaking,
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = mock_tests)]
+pub struct NewMockTest {
+    pub space_id: i32,
+    pub creator_id: i32,
+    pub name: String,
+    pub updated_at: i64,
+    pub created_at: i64,
+}
+
+impl NewMockTest {
+    pub fn new(space_id: i32, creator_id: i32, name: String) -> Self {
+        Self {
+            space_id,
+            creator_id,
+            name,
+            updated_at: get_now_as_secs(),
+            created_at: get_now_as_secs(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Queryable, SimpleObject)]
+#[graphql(complex)]
+pub struct MockTest {
+    pub id: i32,
+    pub space_id: i32,
+    pub creator_id: i32,
+    pub name: String,
+    pub updated_at: i64,
+    pub created_at: i64,
+}
+
+impl MockTest {
+    pub fn insert(conn: &mut PgConnection, item: NewMockTest) -> Result<Self, Error> {
+        diesel::insert_into(mock_tests::table)
+            .values(item)
+            .get_result(conn)
+    }
+
+    pub fn update_name(
+        conn: &mut PgConnection,
+        mock_test_id: i32,
+        name: String,
+    ) -> Result<Self, Error> {
+        diesel::update(mock_tests::table.find(mock_test_id))
+            .set((
+                mock_tests::name.eq(name),
+                mock_tests::updated_at.eq(get_now_as_secs()),
+            ))
+            .get_result(conn)
+    }
+
+    pub fn find_by_id(conn: &mut PgConnection, mock_test_id: i32) -> Result<Self, Error> {
+        mock_tests::table.find(mock_test_id).first(conn)
+    }
+
+    pub fn find_all_by_space(conn: &mut PgConnection, space_id: i32) -> Result<Vec<Self>, Error> {
+        mock_tests::table
+            .filter(mock_tests::space_id.eq(space_id))
+            .order(mock_tests::created_at.desc())
+            .get_results(conn)
+    }
+
+    pub fn remove(conn: &mut PgConnection, mock_test_id: i32) -> Result<(), Error> {
+        diesel::delete(mock_tests::table.find(mock_test_id)).execute(conn)?;
+        Ok(())
+    }
+}
+
+// Each skill of mock test links to one assignment document
+#[derive(Debug, Clone, Insertable, Queryable, SimpleObject)]
+#[diesel(table_name = mock_test_sections)]
+#[graphql(complex)]
+pub struct MockTestSection {
+    pub mock_test_id: i32,
+    pub skill: MockTestSkill,
+    pub document_id: Uuid,
+    pub updated_at: i64,
+    pub created_at: i64,
+}
+
+impl MockTestSection {
+    pub fn new(mock_test_id: i32, skill: MockTestSkill, document_id: Uuid) -> Self {
+        Self {
+            mock_test_id,
+            skill,
+            document_id,
+            updated_at: get_now_as_secs(),
+            created_at: get_now_as_secs(),
+        }
+    }
+
+    pub fn upsert(conn: &mut PgConnection, item: Self) -> Result<Self, Error> {
+        diesel::insert_into(mock_test_sections::table)
+            .values(&item)
+            .on_conflict((mock_test_sections::mock_test_id, mock_test_sections::skill))
+            .do_update()
+            .set((
+                mock_test_sections::document_id.eq(&item.document_id),
+                mock_test_sections::updated_at.eq(get_now_as_secs()),
+            ))
+            .get_result(conn)
+    }
+
+    pub fn find_all_by_mock_test(
+        conn: &mut PgConnection,
+        mock_test_id: i32,
+    ) -> Result<Vec<Self>, Error> {
+        mock_test_sections::table
+            .filter(mock_test_sections::mock_test_id.eq(mock_test_id))
+            .order(mock_test_sections::skill.asc())
+            .get_results(conn)
+    }
+
+    pub fn remove(
+        conn: &mut PgConnection,
+        mock_test_id: i32,
+        skill: MockTestSkill,
+    ) -> Result<(), Error> {
+        diesel::delete(mock_test_sections::table.find((mock_test_id, skill))).execute(conn)?;
+        Ok(())
+    }
+}
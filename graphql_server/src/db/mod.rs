@@ -1,27 +1,47 @@
 pub mod assignment;
 pub mod band_score;
 pub mod document;
+pub mod feedback_annotation;
 pub mod file;
+pub mod file_comment;
+pub mod mock_test;
 pub mod notification;
 pub mod page;
+pub mod peer_review;
+pub mod quiz;
+pub mod regrade_request;
 pub mod rubric;
+pub mod rubric_template;
 pub mod schema;
 pub mod space;
 pub mod space_member;
 pub mod submission;
+pub mod submission_feedback_file;
+pub mod submission_grade_history;
+pub mod submission_marking;
 pub mod user;
 pub mod writing_block;
 
 pub use assignment::*;
 pub use band_score::*;
 pub use document::*;
+pub use feedback_annotation::*;
 pub use file::*;
+pub use file_comment::*;
+pub use mock_test::*;
 pub use notification::*;
 pub use page::*;
+pub use peer_review::*;
+pub use quiz::*;
+pub use regrade_request::*;
 pub use rubric::*;
+pub use rubric_template::*;
 pub use space::*;
 pub use space_member::*;
 pub use submission::*;
+pub use submission_feedback_file::*;
+pub use submission_grade_history::*;
+pub use submission_marking::*;
 pub use user::*;
 pub use writing_block::*;
 
@@ -20,6 +20,8 @@ pub enum NotificationType {
     SubmitSubmission,
     FeedbackSubmission,
     AssignToAssignment,
+    FeedbackSubmissions,
+    RegradeRequest,
 }
 
 impl_enum_for_db!(NotificationType);
@@ -61,6 +63,14 @@ impl Notification {
         Self::new(NotificationType::FeedbackSubmission, context)
     }
 
+    pub fn new_feedback_submissions_notification(context: FeedbackSubmissionsContext) -> Self {
+        Self::new(NotificationType::FeedbackSubmissions, context)
+    }
+
+    pub fn new_regrade_request_notification(context: RegradeRequestContext) -> Self {
+        Self::new(NotificationType::RegradeRequest, context)
+    }
+
     pub fn new_assign_to_assignment_notification(context: AssignToAssignmentContext) -> Self {
         Self::new(NotificationType::AssignToAssignment, context)
     }
@@ -159,6 +169,41 @@ Great news! Your teacher has provided feedback on your submission in {submission
     }
 }
 
+// Feedback of many submissions of a student, sent once by bulk grading
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedbackSubmissionsContext {
+    pub submissions: Vec<FeedbackSubmissionContext>,
+}
+
+impl ContextMessage for FeedbackSubmissionsContext {
+    fn get_title(&self) -> String {
+        "📝 Feedback Alert! 📝".to_string()
+    }
+
+    fn get_message(&self) -> String {
+        let submission_names = self
+            .submissions
+            .iter()
+            .map(|submission| submission.submission_name.as_str())
+            .collect::<Vec<&str>>()
+            .join(", ");
+        format!(
+            r#"
+Great news! Your teacher has provided feedback on {count} of your submissions: {submission_names}. Make sure to review them and let us know if you have any questions. Keep up the excellent work! 👍
+        "#,
+            count = self.submissions.len(),
+            submission_names = submission_names,
+        )
+    }
+
+    fn get_url_path(&self, receiver: &User) -> String {
+        self.submissions
+            .first()
+            .map(|submission| submission.get_url_path(receiver))
+            .unwrap_or_default()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AssignToAssignmentContext {
     pub assignment_document_id: Uuid,
@@ -184,6 +229,35 @@ Hello there! You've been assigned to a assignment: {assignment_name}. If you hav
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegradeRequestContext {
+    pub document_submission_id: Uuid,
+    pub submission_name: String,
+    pub student_name: String,
+    pub reason: String,
+}
+
+impl ContextMessage for RegradeRequestContext {
+    fn get_title(&self) -> String {
+        "🔁 Regrade Request! 🔁".to_string()
+    }
+
+    fn get_message(&self) -> String {
+        format!(
+            r#"
+"{student_name}" has requested a regrade of their submission in {submission_name}: "{reason}". Please review it and accept or reject the request. 📝
+        "#,
+            student_name = self.student_name,
+            submission_name = self.submission_name,
+            reason = self.reason,
+        )
+    }
+
+    fn get_url_path(&self, _: &User) -> String {
+        format_document_url(self.document_submission_id)
+    }
+}
+
 #[derive(Debug, Clone, Insertable, Queryable, SimpleObject)]
 #[diesel(table_name = notification_receivers)]
 pub struct NotificationReceiver {
@@ -34,7 +34,7 @@ pub struct Page {
     pub index: i32,
     pub title: String,
     pub layout: PageLayout,
-    #[graphql(skip_input)]
+    #[graphql(skip)]
     pub created_by_id: i32,
     #[graphql(skip_input)]
     pub deleted_at: Option<i64>,
@@ -161,6 +161,18 @@ impl PageContent {
             .get_results(conn)
     }
 
+    // Return pairs of page content id and its document id
+    pub fn find_document_ids(
+        conn: &mut PgConnection,
+        page_content_ids: Vec<Uuid>,
+    ) -> Result<Vec<(Uuid, Uuid)>, Error> {
+        page_contents::table
+            .inner_join(pages::table)
+            .filter(page_contents::id.eq_any(page_content_ids))
+            .select((page_contents::id, pages::document_id))
+            .get_results(conn)
+    }
+
     pub fn get_json_content(&self) -> JSONContent {
         serde_json::from_value::<JSONContent>(self.body.clone()).unwrap_or_default()
     }
@@ -230,6 +242,28 @@ impl JSONContent {
         }
     }
 
+    pub fn find_block_ids(&self, block_name: &str, key_id: &str) -> Vec<Uuid> {
+        let mut result = vec![];
+        if self.content_type.as_deref() == Some(block_name) {
+            if let Some(id) = self
+                .attrs
+                .as_ref()
+                .and_then(|attrs| attrs.get(key_id))
+                .and_then(|id| serde_json::from_value::<Uuid>(id.clone()).ok())
+            {
+                result.push(id);
+            }
+        }
+
+        if let Some(contents) = self.content.as_ref() {
+            for content in contents {
+                result.append(&mut content.find_block_ids(block_name, key_id));
+            }
+        }
+
+        result
+    }
+
     pub fn has_file_handler(&self, file_id: Uuid) -> bool {
         let file_value = serde_json::to_value(file_id).unwrap_or_default();
         let predicate =
@@ -0,0 +1,115 @@
+use diesel::result::Error;
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
+
+use super::schema::{assignment_submissions, peer_reviews};
+use super::RubricTableData;
+use crate::util::get_now_as_secs;
+
+// Review of a submission by another student, reviewer is hidden from author of submission
+#[derive(Debug, Clone, Insertable, Queryable, SimpleObject)]
+#[diesel(table_name = peer_reviews)]
+#[graphql(complex)]
+pub struct PeerReview {
+    pub submission_id: i32,
+    #[graphql(skip)]
+    pub reviewer_id: i32,
+    pub graded_data: Option<RubricTableData>,
+    // Total rubric score of graded data
+    pub score: Option<f64>,
+    pub submitted_at: Option<i64>,
+    pub updated_at: i64,
+    pub created_at: i64,
+}
+
+impl PeerReview {
+    pub fn new(submission_id: i32, reviewer_id: i32) -> Self {
+        Self {
+            submission_id,
+            reviewer_id,
+            graded_data: None,
+            score: None,
+            submitted_at: None,
+            updated_at: get_now_as_secs(),
+            created_at: get_now_as_secs(),
+        }
+    }
+
+    pub fn insert_many(conn: &mut PgConnection, items: Vec<Self>) -> Result<usize, Error> {
+        diesel::insert_into(peer_reviews::table)
+            .values(items)
+            .on_conflict_do_nothing()
+            .execute(conn)
+    }
+
+    pub fn submit(
+        conn: &mut PgConnection,
+        submission_id: i32,
+        reviewer_id: i32,
+        graded_data: RubricTableData,
+    ) -> Result<Self, Error> {
+        let score = graded_data.total_rubric_score();
+        diesel::update(peer_reviews::table.find((submission_id, reviewer_id)))
+            .set((
+                peer_reviews::graded_data.eq(graded_data),
+                peer_reviews::score.eq(score),
+                peer_reviews::submitted_at.eq(get_now_as_secs()),
+                peer_reviews::updated_at.eq(get_now_as_secs()),
+            ))
+            .get_result(conn)
+    }
+
+    pub fn find(
+        conn: &mut PgConnection,
+        submission_id: i32,
+        reviewer_id: i32,
+    ) -> Result<Self, Error> {
+        peer_reviews::table
+            .find((submission_id, reviewer_id))
+            .first(conn)
+    }
+
+    pub fn find_all_by_submission(
+        conn: &mut PgConnection,
+        submission_id: i32,
+    ) -> Result<Vec<Self>, Error> {
+        peer_reviews::table
+            .filter(peer_reviews::submission_id.eq(submission_id))
+            .order(peer_reviews::created_at.asc())
+            .get_results(conn)
+    }
+
+    pub fn find_all_by_reviewer_and_assignment(
+        conn: &mut PgConnection,
+        reviewer_id: i32,
+        assignment_id: i32,
+    ) -> Result<Vec<Self>, Error> {
+        let submission_ids = assignment_submissions::table
+            .filter(assignment_submissions::assignment_id.eq(assignment_id))
+            .select(assignment_submissions::id);
+        peer_reviews::table
+            .filter(peer_reviews::reviewer_id.eq(reviewer_id))
+            .filter(peer_reviews::submission_id.eq_any(submission_ids))
+            .order(peer_reviews::created_at.asc())
+            .get_results(conn)
+    }
+
+    pub fn find_all_reviewer_ids_by_submission(
+        conn: &mut PgConnection,
+        submission_id: i32,
+    ) -> Result<Vec<i32>, Error> {
+        peer_reviews::table
+            .filter(peer_reviews::submission_id.eq(submission_id))
+            .select(peer_reviews::reviewer_id)
+            .get_results(conn)
+    }
+
+    // Average score of submitted reviews, None if no review is submitted yet
+    pub fn average_score(reviews: &[Self]) -> Option<f64> {
+        let scores: Vec<f64> = reviews.iter().filter_map(|review| review.score).collect();
+        if scores.is_empty() {
+            return None;
+        }
+
+        Some(scores.iter().sum::<f64>() / scores.len() as f64)
+    }
+}
@@ -0,0 +1,412 @@
+use diesel::result::Error;
+use diesel::sql_types::{Integer, Jsonb};
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use super::schema::{quiz_answer_keys, quiz_blocks};
+use crate::util::get_now_as_secs;
+use crate::{impl_enum_for_db, impl_jsonb_for_db};
+
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, FromPrimitive, ToPrimitive, AsExpression, FromSqlRow, Enum,
+)]
+#[diesel(sql_type = Integer)]
+pub enum QuizType {
+    SingleChoice,
+    MultipleChoice,
+    FillInBlank,
+    TrueFalseNotGiven,
+    Matching,
+}
+
+impl_enum_for_db!(QuizType);
+
+impl Default for QuizType {
+    fn default() -> Self {
+        Self::SingleChoice
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "QuizMatchingPairInput")]
+pub struct QuizMatchingPair {
+    pub left: String,
+    pub right: String,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    Serialize,
+    Deserialize,
+    SimpleObject,
+    InputObject,
+    AsExpression,
+    FromSqlRow,
+)]
+#[diesel(sql_type = Jsonb)]
+#[graphql(input_name = "QuizAnswerInput")]
+pub struct QuizAnswer {
+    // Single choice, multiple choice, true/false/not given -> selected option ids
+    #[serde(default)]
+    pub choices: Vec<String>,
+    // Fill in blank -> text by blank index. Accepted alternatives of key are separated by "/"
+    #[serde(default)]
+    pub blanks: Vec<String>,
+    // Matching -> left item id with right item id
+    #[serde(default)]
+    pub pairs: Vec<QuizMatchingPair>,
+}
+
+impl QuizAnswer {
+    pub fn grade(&self, key: &QuizAnswer, quiz_type: QuizType, max_score: f64) -> f64 {
+        let ratio = match quiz_type {
+            QuizType::SingleChoice | QuizType::TrueFalseNotGiven => {
+                let choices = unique_choices(&self.choices);
+                if !key.choices.is_empty() && choices == unique_choices(&key.choices) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            QuizType::MultipleChoice => {
+                let key_choices = unique_choices(&key.choices);
+                if key_choices.is_empty() {
+                    return 0.0;
+                }
+                // Each wrong pick cancels a correct one to avoid selecting everything
+                let choices = unique_choices(&self.choices);
+                let correct = choices.intersection(&key_choices).count() as f64;
+                let wrong = choices.len() as f64 - correct;
+                (correct - wrong).max(0.0) / key_choices.len() as f64
+            }
+            QuizType::FillInBlank => {
+                if key.blanks.is_empty() {
+                    return 0.0;
+                }
+                let correct = key
+                    .blanks
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, accepted)| {
+                        self.blanks.get(*index).is_some_and(|blank| {
+                            let blank = normalize_text(blank);
+                            accepted
+                                .split('/')
+                                .any(|item| normalize_text(item) == blank)
+                        })
+                    })
+                    .count() as f64;
+                correct / key.blanks.len() as f64
+            }
+            QuizType::Matching => {
+                if key.pairs.is_empty() {
+                    return 0.0;
+                }
+                // Each left item accepts only one right item
+                let pairs = self
+                    .pairs
+                    .iter()
+                    .map(|pair| (pair.left.as_str(), pair.right.as_str()))
+                    .collect::<HashSet<(&str, &str)>>();
+                let lefts = pairs
+                    .iter()
+                    .map(|(left, _)| *left)
+                    .collect::<HashSet<&str>>();
+                if lefts.len() != pairs.len() {
+                    return 0.0;
+                }
+                let correct = key
+                    .pairs
+                    .iter()
+                    .filter(|pair| pairs.contains(&(pair.left.as_str(), pair.right.as_str())))
+                    .count() as f64;
+                correct / key.pairs.len() as f64
+            }
+        };
+
+        ratio * max_score
+    }
+}
+
+fn unique_choices(choices: &[String]) -> HashSet<&str> {
+    choices.iter().map(|choice| choice.as_str()).collect()
+}
+
+fn normalize_text(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+impl_jsonb_for_db!(QuizAnswer);
+
+#[derive(Debug, Clone, Insertable, Queryable, SimpleObject, InputObject)]
+#[graphql(input_name = "QuizBlockInput", complex)]
+#[diesel(table_name = quiz_blocks)]
+pub struct QuizBlock {
+    pub id: Uuid,
+    #[graphql(skip_input)]
+    pub page_content_id: Uuid,
+    #[graphql(skip)]
+    pub creator_id: i32,
+    pub quiz_type: QuizType,
+    // Question and options, visible to students
+    pub content: serde_json::Value,
+    pub score: f64,
+    // Answer of student in submission
+    #[graphql(skip_input)]
+    pub answer: Option<QuizAnswer>,
+    #[graphql(skip_input)]
+    pub original_quiz_block_id: Option<Uuid>,
+    #[graphql(skip_input)]
+    pub updated_at: i64,
+    #[graphql(skip_input)]
+    pub created_at: i64,
+}
+
+impl QuizBlock {
+    pub fn upsert(conn: &mut PgConnection, mut item: Self) -> Result<Self, Error> {
+        item.created_at = get_now_as_secs();
+        item.updated_at = get_now_as_secs();
+
+        diesel::insert_into(quiz_blocks::table)
+            .values(&item)
+            .on_conflict(quiz_blocks::id)
+            .do_update()
+            .set((
+                quiz_blocks::page_content_id.eq(&item.page_content_id),
+                quiz_blocks::creator_id.eq(&item.creator_id),
+                quiz_blocks::quiz_type.eq(&item.quiz_type),
+                quiz_blocks::content.eq(&item.content),
+                quiz_blocks::score.eq(&item.score),
+                quiz_blocks::updated_at.eq(&item.updated_at),
+            ))
+            .get_result(conn)
+    }
+
+    pub fn update_answer(
+        conn: &mut PgConnection,
+        id: Uuid,
+        answer: QuizAnswer,
+    ) -> Result<Self, Error> {
+        diesel::update(quiz_blocks::table.find(id))
+            .set((
+                quiz_blocks::answer.eq(answer),
+                quiz_blocks::updated_at.eq(get_now_as_secs()),
+            ))
+            .get_result(conn)
+    }
+
+    pub fn find(conn: &mut PgConnection, id: Uuid) -> Result<Self, Error> {
+        quiz_blocks::table.find(id).first(conn)
+    }
+
+    pub fn find_all_by_ids(conn: &mut PgConnection, ids: &[Uuid]) -> Result<Vec<Self>, Error> {
+        quiz_blocks::table
+            .filter(quiz_blocks::id.eq_any(ids))
+            .get_results(conn)
+    }
+
+    pub fn find_all_by_page_content(
+        conn: &mut PgConnection,
+        page_content_id: Uuid,
+    ) -> Result<Vec<Self>, Error> {
+        quiz_blocks::table
+            .filter(quiz_blocks::page_content_id.eq(page_content_id))
+            .get_results(conn)
+    }
+
+    pub fn find_all_by_page_contents(
+        conn: &mut PgConnection,
+        page_content_ids: &[Uuid],
+    ) -> Result<Vec<Self>, Error> {
+        quiz_blocks::table
+            .filter(quiz_blocks::page_content_id.eq_any(page_content_ids))
+            .get_results(conn)
+    }
+}
+
+// Answer key is kept apart from quiz block, only readable with view answer permission
+#[derive(Debug, Clone, Insertable, Queryable, SimpleObject)]
+#[diesel(table_name = quiz_answer_keys)]
+pub struct QuizAnswerKey {
+    pub quiz_block_id: Uuid,
+    pub answer: QuizAnswer,
+    pub updated_at: i64,
+    pub created_at: i64,
+}
+
+impl QuizAnswerKey {
+    pub fn new(quiz_block_id: Uuid, answer: QuizAnswer) -> Self {
+        Self {
+            quiz_block_id,
+            answer,
+            updated_at: get_now_as_secs(),
+            created_at: get_now_as_secs(),
+        }
+    }
+
+    pub fn upsert(conn: &mut PgConnection, item: Self) -> Result<Self, Error> {
+        diesel::insert_into(quiz_answer_keys::table)
+            .values(&item)
+            .on_conflict(quiz_answer_keys::quiz_block_id)
+            .do_update()
+            .set((
+                quiz_answer_keys::answer.eq(&item.answer),
+                quiz_answer_keys::updated_at.eq(get_now_as_secs()),
+            ))
+            .get_result(conn)
+    }
+
+    pub fn find_opt(conn: &mut PgConnection, quiz_block_id: Uuid) -> Result<Option<Self>, Error> {
+        match quiz_answer_keys::table.find(quiz_block_id).first(conn) {
+            Ok(answer_key) => Ok(Some(answer_key)),
+            Err(Error::NotFound) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn find_all_by_quiz_blocks(
+        conn: &mut PgConnection,
+        quiz_block_ids: &[Uuid],
+    ) -> Result<Vec<Self>, Error> {
+        quiz_answer_keys::table
+            .filter(quiz_answer_keys::quiz_block_id.eq_any(quiz_block_ids))
+            .get_results(conn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Avoid the actix test macro
+    use std::prelude::v1::test;
+
+    use super::*;
+
+    fn choices(items: &[&str]) -> QuizAnswer {
+        QuizAnswer {
+            choices: items.iter().map(|item| item.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn pairs(items: &[(&str, &str)]) -> QuizAnswer {
+        QuizAnswer {
+            pairs: items
+                .iter()
+                .map(|(left, right)| QuizMatchingPair {
+                    left: left.to_string(),
+                    right: right.to_string(),
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn blanks(items: &[&str]) -> QuizAnswer {
+        QuizAnswer {
+            blanks: items.iter().map(|item| item.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn grade_single_choice() {
+        let key = choices(&["a"]);
+        assert_eq!(
+            choices(&["a"]).grade(&key, QuizType::SingleChoice, 2.0),
+            2.0
+        );
+        assert_eq!(
+            choices(&["b"]).grade(&key, QuizType::SingleChoice, 2.0),
+            0.0
+        );
+        assert_eq!(
+            choices(&["a", "a"]).grade(&key, QuizType::SingleChoice, 2.0),
+            2.0
+        );
+        assert_eq!(
+            choices(&["a", "b"]).grade(&key, QuizType::SingleChoice, 2.0),
+            0.0
+        );
+        assert_eq!(
+            choices(&[]).grade(&choices(&[]), QuizType::SingleChoice, 2.0),
+            0.0
+        );
+    }
+
+    #[test]
+    fn grade_multiple_choice() {
+        let key = choices(&["a", "b"]);
+        assert_eq!(
+            choices(&["a", "b"]).grade(&key, QuizType::MultipleChoice, 2.0),
+            2.0
+        );
+        assert_eq!(
+            choices(&["a"]).grade(&key, QuizType::MultipleChoice, 2.0),
+            1.0
+        );
+        assert_eq!(
+            choices(&["a", "c"]).grade(&key, QuizType::MultipleChoice, 2.0),
+            0.0
+        );
+        assert_eq!(
+            choices(&["a", "b", "c", "d"]).grade(&key, QuizType::MultipleChoice, 2.0),
+            0.0
+        );
+        assert_eq!(
+            choices(&["a", "a", "c"]).grade(&key, QuizType::MultipleChoice, 2.0),
+            0.0
+        );
+        assert_eq!(
+            choices(&["a", "a"]).grade(&key, QuizType::MultipleChoice, 2.0),
+            1.0
+        );
+    }
+
+    #[test]
+    fn grade_fill_in_blank() {
+        let key = blanks(&["colour/color", "cat"]);
+        assert_eq!(
+            blanks(&[" Color ", "cat"]).grade(&key, QuizType::FillInBlank, 2.0),
+            2.0
+        );
+        assert_eq!(
+            blanks(&["colour"]).grade(&key, QuizType::FillInBlank, 2.0),
+            1.0
+        );
+        assert_eq!(
+            blanks(&["cat", "colour"]).grade(&key, QuizType::FillInBlank, 2.0),
+            0.0
+        );
+    }
+
+    #[test]
+    fn grade_matching() {
+        let key = pairs(&[("1", "a"), ("2", "b")]);
+        assert_eq!(
+            pairs(&[("1", "a"), ("2", "b")]).grade(&key, QuizType::Matching, 2.0),
+            2.0
+        );
+        assert_eq!(
+            pairs(&[("1", "a"), ("2", "a")]).grade(&key, QuizType::Matching, 2.0),
+            1.0
+        );
+        assert_eq!(
+            pairs(&[("1", "a"), ("1", "a")]).grade(&key, QuizType::Matching, 2.0),
+            1.0
+        );
+        assert_eq!(
+            pairs(&[("1", "a"), ("1", "b"), ("2", "a"), ("2", "b")]).grade(
+                &key,
+                QuizType::Matching,
+                2.0
+            ),
+            0.0
+        );
+    }
+}
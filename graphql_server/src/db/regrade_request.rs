@@ -0,0 +1,119 @@
+use diesel::result::Error;
+use diesel::sql_types::Integer;
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
+
+use super::schema::regrade_requests;
+use crate::impl_enum_for_db;
+use crate::util::get_now_as_secs;
+
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, FromPrimitive, ToPrimitive, AsExpression, FromSqlRow, Enum,
+)]
+#[diesel(sql_type = Integer)]
+pub enum RegradeRequestStatus {
+    Pending,
+    // Submission is returned to grading
+    Accepted,
+    Rejected,
+}
+
+impl_enum_for_db!(RegradeRequestStatus);
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = regrade_requests)]
+pub struct NewRegradeRequest {
+    pub submission_id: i32,
+    pub requester_id: i32,
+    pub reason: String,
+    pub criterion: Option<String>,
+    pub status: RegradeRequestStatus,
+    pub updated_at: i64,
+    pub created_at: i64,
+}
+
+impl NewRegradeRequest {
+    pub fn new(
+        submission_id: i32,
+        requester_id: i32,
+        reason: String,
+        criterion: Option<String>,
+    ) -> Self {
+        Self {
+            submission_id,
+            requester_id,
+            reason,
+            criterion,
+            status: RegradeRequestStatus::Pending,
+            updated_at: get_now_as_secs(),
+            created_at: get_now_as_secs(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Queryable, SimpleObject)]
+#[graphql(complex)]
+pub struct RegradeRequest {
+    pub id: i32,
+    pub submission_id: i32,
+    pub requester_id: i32,
+    pub reason: String,
+    // Rubric criterion which student contests, None if contesting whole grade
+    pub criterion: Option<String>,
+    pub status: RegradeRequestStatus,
+    pub responder_id: Option<i32>,
+    pub response: Option<String>,
+    pub responded_at: Option<i64>,
+    pub updated_at: i64,
+    pub created_at: i64,
+}
+
+impl RegradeRequest {
+    pub fn insert(conn: &mut PgConnection, item: NewRegradeRequest) -> Result<Self, Error> {
+        diesel::insert_into(regrade_requests::table)
+            .values(item)
+            .get_result(conn)
+    }
+
+    pub fn respond(
+        conn: &mut PgConnection,
+        id: i32,
+        status: RegradeRequestStatus,
+        responder_id: i32,
+        response: Option<String>,
+    ) -> Result<Self, Error> {
+        diesel::update(regrade_requests::table.find(id))
+            .set((
+                regrade_requests::status.eq(status),
+                regrade_requests::responder_id.eq(responder_id),
+                regrade_requests::response.eq(response),
+                regrade_requests::responded_at.eq(get_now_as_secs()),
+                regrade_requests::updated_at.eq(get_now_as_secs()),
+            ))
+            .get_result(conn)
+    }
+
+    pub fn find_by_id(conn: &mut PgConnection, id: i32) -> Result<Self, Error> {
+        regrade_requests::table.find(id).first(conn)
+    }
+
+    pub fn find_all_by_submission(
+        conn: &mut PgConnection,
+        submission_id: i32,
+    ) -> Result<Vec<Self>, Error> {
+        regrade_requests::table
+            .filter(regrade_requests::submission_id.eq(submission_id))
+            .order(regrade_requests::created_at.desc())
+            .get_results(conn)
+    }
+
+    pub fn find_all_pending_by_submissions(
+        conn: &mut PgConnection,
+        submission_ids: &[i32],
+    ) -> Result<Vec<Self>, Error> {
+        regrade_requests::table
+            .filter(regrade_requests::submission_id.eq_any(submission_ids))
+            .filter(regrade_requests::status.eq(RegradeRequestStatus::Pending))
+            .order(regrade_requests::created_at.asc())
+            .get_results(conn)
+    }
+}
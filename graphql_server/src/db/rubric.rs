@@ -3,7 +3,8 @@ use diesel::sql_types::Jsonb;
 use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
 use uuid::Uuid;
 
-use super::schema::{rubric_submissions, rubrics};
+use super::round_ielts_band;
+use super::schema::{rubric_submissions, rubric_versions, rubrics};
 use crate::impl_jsonb_for_db;
 use crate::util::get_now_as_secs;
 
@@ -11,6 +12,8 @@ use crate::util::get_now_as_secs;
 pub enum RubricType {
     PointBased,
     PointRange,
+    // Band of each criteria is averaged then rounded to half band, e.g. IELTS Writing, Speaking
+    IeltsBandAverage,
 }
 
 impl Default for RubricType {
@@ -66,6 +69,13 @@ pub struct RubricTableData {
 
 impl RubricTableData {
     pub fn total_rubric_score(&self) -> f64 {
+        match self.rubric_type {
+            RubricType::PointBased | RubricType::PointRange => self.weighted_rubric_score(),
+            RubricType::IeltsBandAverage => self.band_average_rubric_score(),
+        }
+    }
+
+    fn weighted_rubric_score(&self) -> f64 {
         let mut score = 0.0;
 
         for (index, inner_items) in self.items.iter().enumerate() {
@@ -83,6 +93,158 @@ impl RubricTableData {
 
         score
     }
+
+    // Criteria which are not picked yet do not count as band 0
+    fn band_average_rubric_score(&self) -> f64 {
+        let bands: Vec<f64> = (0..self.criteria.len())
+            .filter_map(|index| self.criteria_score(index))
+            .collect();
+        if bands.is_empty() {
+            return 0.0;
+        }
+
+        let average = bands.iter().sum::<f64>() / bands.len() as f64;
+        round_ielts_band(average)
+    }
+
+    // Items are [Criteria][Level], weighting is optional but must cover every criteria if set
+    pub fn validate_structure(&self) -> Result<(), String> {
+        if self.criteria.is_empty() || self.level.is_empty() {
+            return Err("Rubric needs at least one criteria and one level".into());
+        }
+        if self.items.len() != self.criteria.len() {
+            return Err(format!(
+                "Rubric has {} criteria but {} rows of items",
+                self.criteria.len(),
+                self.items.len()
+            ));
+        }
+        if let Some((index, _)) = self
+            .items
+            .iter()
+            .enumerate()
+            .find(|(_, items)| items.len() != self.level.len())
+        {
+            return Err(format!(
+                "Criteria \"{}\" must have {} items, one for each level",
+                self.criteria[index],
+                self.level.len()
+            ));
+        }
+        if !self.weighting_criteria.is_empty()
+            && self.weighting_criteria.len() != self.criteria.len()
+        {
+            return Err(format!(
+                "Rubric has {} criteria but {} weightings",
+                self.criteria.len(),
+                self.weighting_criteria.len()
+            ));
+        }
+
+        Ok(())
+    }
+
+    // Graded data must keep the structure of rubric and only change picks.
+    // Each criteria has at most one selected level, whose score belongs to the level.
+    pub fn validate_grading(&self, rubric: &RubricTableData) -> Result<(), String> {
+        if self.rubric_type != rubric.rubric_type
+            || self.criteria != rubric.criteria
+            || self.level != rubric.level
+            || self.weighting_criteria != rubric.weighting_criteria
+            || self.items.len() != rubric.items.len()
+        {
+            return Err("Graded data does not match the structure of rubric".into());
+        }
+
+        for (criteria_index, (items, rubric_items)) in
+            self.items.iter().zip(&rubric.items).enumerate()
+        {
+            let criteria_name = &self.criteria[criteria_index];
+            if items.len() != rubric_items.len() {
+                return Err(format!(
+                    "Criteria \"{criteria_name}\" does not match the levels of rubric"
+                ));
+            }
+            if items.iter().filter(|item| item.user_pick.selected).count() > 1 {
+                return Err(format!(
+                    "Criteria \"{criteria_name}\" can select only one level"
+                ));
+            }
+            // Band average needs a band of every criteria
+            if self.rubric_type == RubricType::IeltsBandAverage
+                && !items.iter().any(|item| item.user_pick.selected)
+            {
+                return Err(format!("Criteria \"{criteria_name}\" must select a level"));
+            }
+
+            for (item, rubric_item) in items.iter().zip(rubric_items) {
+                if item.score != rubric_item.score || item.to_score != rubric_item.to_score {
+                    return Err(format!(
+                        "Criteria \"{criteria_name}\" does not match the scores of rubric"
+                    ));
+                }
+                if !item.user_pick.selected {
+                    continue;
+                }
+
+                let is_valid_score = match self.rubric_type {
+                    RubricType::PointRange => {
+                        let from = item.score.min(item.to_score);
+                        let to = item.score.max(item.to_score);
+                        from <= item.user_pick.score && item.user_pick.score <= to
+                    }
+                    RubricType::PointBased | RubricType::IeltsBandAverage => {
+                        item.user_pick.score == item.score
+                    }
+                };
+                if !is_valid_score {
+                    return Err(format!(
+                        "Score {} is not valid for criteria \"{criteria_name}\"",
+                        item.user_pick.score
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Keep picks of criteria and level which still exist by name in the new structure
+    pub fn carry_picks_from(&mut self, previous: &RubricTableData) {
+        for (criteria_index, criteria_name) in self.criteria.iter().enumerate() {
+            let previous_items = previous
+                .criteria
+                .iter()
+                .position(|name| name == criteria_name)
+                .and_then(|index| previous.items.get(index));
+            if let (Some(previous_items), Some(items)) =
+                (previous_items, self.items.get_mut(criteria_index))
+            {
+                for (level_index, item) in items.iter_mut().enumerate() {
+                    let previous_item = self
+                        .level
+                        .get(level_index)
+                        .and_then(|level_name| {
+                            previous.level.iter().position(|name| name == level_name)
+                        })
+                        .and_then(|index| previous_items.get(index));
+                    if let Some(previous_item) = previous_item {
+                        item.user_pick = previous_item.user_pick.clone();
+                    }
+                }
+            }
+        }
+    }
+
+    // Selected score of one criteria without weighting, None if teacher has not picked yet
+    pub fn criteria_score(&self, criteria_index: usize) -> Option<f64> {
+        self.items
+            .get(criteria_index)?
+            .iter()
+            .filter(|item| item.user_pick.selected)
+            .map(|item| item.user_pick.score)
+            .reduce(|a, b| a + b)
+    }
 }
 
 impl_jsonb_for_db!(RubricTableData);
@@ -100,9 +262,27 @@ pub struct Rubric {
     pub created_at: i64,
     #[graphql(skip_input)]
     pub user_id: i32,
+    // Latest version, increased whenever data changes
+    #[graphql(skip_input)]
+    pub version: i32,
+    // Teachers of this space can view and use the rubric
+    pub space_id: Option<i32>,
 }
 
 impl Rubric {
+    pub fn new(user_id: i32, name: String, data: RubricTableData) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            data,
+            updated_at: get_now_as_secs(),
+            created_at: get_now_as_secs(),
+            user_id,
+            version: 1,
+            space_id: None,
+        }
+    }
+
     pub fn upsert(conn: &mut PgConnection, mut item: Self) -> Result<Self, Error> {
         item.updated_at = get_now_as_secs();
         item.created_at = get_now_as_secs();
@@ -114,6 +294,8 @@ impl Rubric {
                 rubrics::updated_at.eq(item.updated_at),
                 rubrics::name.eq(&item.name),
                 rubrics::data.eq(&item.data),
+                rubrics::version.eq(item.version),
+                rubrics::space_id.eq(item.space_id),
             ))
             .get_result(conn)
     }
@@ -122,18 +304,84 @@ impl Rubric {
         rubrics::table.find(rubric_id).first(conn)
     }
 
+    pub fn find_by_id_opt(conn: &mut PgConnection, rubric_id: Uuid) -> Result<Option<Self>, Error> {
+        match Self::find_by_id(conn, rubric_id) {
+            Ok(item) => Ok(Some(item)),
+            Err(Error::NotFound) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn find_all_by_ids(
+        conn: &mut PgConnection,
+        rubric_ids: &[Uuid],
+    ) -> Result<Vec<Self>, Error> {
+        rubrics::table
+            .filter(rubrics::id.eq_any(rubric_ids))
+            .get_results(conn)
+    }
+
     pub fn find_all_by_user(conn: &mut PgConnection, user_id: i32) -> Result<Vec<Self>, Error> {
         rubrics::table
             .filter(rubrics::user_id.eq(user_id))
             .get_results(conn)
     }
 
+    pub fn find_all_by_space(conn: &mut PgConnection, space_id: i32) -> Result<Vec<Self>, Error> {
+        rubrics::table
+            .filter(rubrics::space_id.eq(space_id))
+            .get_results(conn)
+    }
+
     pub fn remove(conn: &mut PgConnection, rubric_id: Uuid) -> Result<(), Error> {
         diesel::delete(rubrics::table.find(rubric_id)).execute(conn)?;
         Ok(())
     }
 }
 
+// Snapshot of rubric data, never updated once created
+#[derive(Debug, Clone, Insertable, Queryable, SimpleObject)]
+#[diesel(table_name = rubric_versions)]
+pub struct RubricVersion {
+    pub rubric_id: Uuid,
+    pub version: i32,
+    pub data: RubricTableData,
+    pub created_at: i64,
+}
+
+impl RubricVersion {
+    pub fn new(rubric: &Rubric) -> Self {
+        Self {
+            rubric_id: rubric.id,
+            version: rubric.version,
+            data: rubric.data.clone(),
+            created_at: get_now_as_secs(),
+        }
+    }
+
+    pub fn insert(conn: &mut PgConnection, item: Self) -> Result<Self, Error> {
+        diesel::insert_into(rubric_versions::table)
+            .values(item)
+            .get_result(conn)
+    }
+
+    pub fn find(conn: &mut PgConnection, rubric_id: Uuid, version: i32) -> Result<Self, Error> {
+        rubric_versions::table
+            .find((rubric_id, version))
+            .first(conn)
+    }
+
+    pub fn find_all_by_rubric(
+        conn: &mut PgConnection,
+        rubric_id: Uuid,
+    ) -> Result<Vec<Self>, Error> {
+        rubric_versions::table
+            .filter(rubric_versions::rubric_id.eq(rubric_id))
+            .order(rubric_versions::version.desc())
+            .get_results(conn)
+    }
+}
+
 #[derive(Debug, Clone, Insertable, Queryable, InputObject, SimpleObject)]
 #[diesel(table_name = rubric_submissions)]
 #[graphql(input_name = "RubricSubmissionInput")]
@@ -145,16 +393,20 @@ pub struct RubricSubmission {
     pub updated_at: i64,
     #[graphql(skip_input)]
     pub created_at: i64,
+    // Version of rubric which graded data is copied from, None if copied before versioning
+    #[graphql(skip_input)]
+    pub rubric_version: Option<i32>,
 }
 
 impl RubricSubmission {
-    pub fn new(submission_id: i32, rubric_id: Uuid, graded_data: RubricTableData) -> Self {
+    pub fn new(submission_id: i32, rubric: &Rubric) -> Self {
         Self {
             submission_id,
-            rubric_id: Some(rubric_id),
-            graded_data,
+            rubric_id: Some(rubric.id),
+            graded_data: rubric.data.clone(),
             updated_at: get_now_as_secs(),
             created_at: get_now_as_secs(),
+            rubric_version: Some(rubric.version),
         }
     }
 
@@ -168,6 +420,7 @@ impl RubricSubmission {
             .set((
                 rubric_submissions::updated_at.eq(&item.updated_at),
                 rubric_submissions::graded_data.eq(&item.graded_data),
+                rubric_submissions::rubric_version.eq(&item.rubric_version),
             ))
             .get_result(conn)
     }
@@ -186,4 +439,13 @@ impl RubricSubmission {
             Err(e) => Err(e),
         }
     }
+
+    pub fn find_all_by_submissions(
+        conn: &mut PgConnection,
+        submission_ids: &[i32],
+    ) -> Result<Vec<Self>, Error> {
+        rubric_submissions::table
+            .filter(rubric_submissions::submission_id.eq_any(submission_ids))
+            .get_results(conn)
+    }
 }
@@ -0,0 +1,149 @@
+use super::{RubricTableData, RubricTableItem, RubricType};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum RubricTemplate {
+    IeltsWritingTask1,
+    IeltsWritingTask2,
+    IeltsSpeaking,
+    Cefr,
+}
+
+const IELTS_BAND_LABELS: [&str; 9] = [
+    "Expert",
+    "Very good",
+    "Good",
+    "Competent",
+    "Modest",
+    "Limited",
+    "Extremely limited",
+    "Intermittent",
+    "Non-user",
+];
+
+const CEFR_LEVELS: [(&str, &str); 6] = [
+    ("C2", "Mastery"),
+    ("C1", "Effective operational proficiency"),
+    ("B2", "Vantage"),
+    ("B1", "Threshold"),
+    ("A2", "Waystage"),
+    ("A1", "Breakthrough"),
+];
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct RubricTemplateItem {
+    pub template: RubricTemplate,
+    pub name: String,
+    pub data: RubricTableData,
+}
+
+impl From<RubricTemplate> for RubricTemplateItem {
+    fn from(template: RubricTemplate) -> Self {
+        Self {
+            template,
+            name: template.name().into(),
+            data: template.data(),
+        }
+    }
+}
+
+impl RubricTemplate {
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::IeltsWritingTask1,
+            Self::IeltsWritingTask2,
+            Self::IeltsSpeaking,
+            Self::Cefr,
+        ]
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::IeltsWritingTask1 => "IELTS Writing Task 1",
+            Self::IeltsWritingTask2 => "IELTS Writing Task 2",
+            Self::IeltsSpeaking => "IELTS Speaking",
+            Self::Cefr => "CEFR Speaking",
+        }
+    }
+
+    pub fn data(&self) -> RubricTableData {
+        match self {
+            Self::IeltsWritingTask1 => ielts_data(&[
+                "Task Achievement",
+                "Coherence and Cohesion",
+                "Lexical Resource",
+                "Grammatical Range and Accuracy",
+            ]),
+            Self::IeltsWritingTask2 => ielts_data(&[
+                "Task Response",
+                "Coherence and Cohesion",
+                "Lexical Resource",
+                "Grammatical Range and Accuracy",
+            ]),
+            Self::IeltsSpeaking => ielts_data(&[
+                "Fluency and Coherence",
+                "Lexical Resource",
+                "Grammatical Range and Accuracy",
+                "Pronunciation",
+            ]),
+            Self::Cefr => cefr_data(&["Range", "Accuracy", "Fluency", "Interaction", "Coherence"]),
+        }
+    }
+}
+
+// Levels from band 9 to band 1, score of each level is its band
+fn ielts_data(criteria: &[&str]) -> RubricTableData {
+    let level = (1..=9).rev().map(|band| format!("Band {band}")).collect();
+    let items = criteria
+        .iter()
+        .map(|criteria_name| {
+            IELTS_BAND_LABELS
+                .iter()
+                .enumerate()
+                .map(|(index, label)| RubricTableItem {
+                    explanation: format!("{label} {}", criteria_name.to_lowercase()),
+                    score: (9 - index) as f64,
+                    ..Default::default()
+                })
+                .collect()
+        })
+        .collect();
+
+    RubricTableData {
+        rubric_type: RubricType::IeltsBandAverage,
+        criteria: criteria.iter().map(|name| name.to_string()).collect(),
+        weighting_criteria: vec![None; criteria.len()],
+        level,
+        items,
+    }
+}
+
+// Levels from C2 = 6 to A1 = 1, criteria are weighted equally so total is the average level
+fn cefr_data(criteria: &[&str]) -> RubricTableData {
+    let level = CEFR_LEVELS
+        .iter()
+        .map(|(level, _)| level.to_string())
+        .collect();
+    let weighting = 1.0 / criteria.len() as f64;
+    let items = criteria
+        .iter()
+        .map(|criteria_name| {
+            CEFR_LEVELS
+                .iter()
+                .enumerate()
+                .map(|(index, (_, label))| RubricTableItem {
+                    explanation: format!("{label}: {}", criteria_name.to_lowercase()),
+                    score: (CEFR_LEVELS.len() - index) as f64,
+                    ..Default::default()
+                })
+                .collect()
+        })
+        .collect();
+
+    RubricTableData {
+        rubric_type: RubricType::PointBased,
+        criteria: criteria.iter().map(|name| name.to_string()).collect(),
+        weighting_criteria: vec![Some(weighting); criteria.len()],
+        level,
+        items,
+    }
+}
@@ -1,5 +1,16 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    assignment_accommodations (assignment_id, user_id) {
+        assignment_id -> Int4,
+        user_id -> Int4,
+        test_duration -> Nullable<Int4>,
+        max_number_of_attempt -> Nullable<Int4>,
+        updated_at -> Int8,
+        created_at -> Int8,
+    }
+}
+
 diesel::table! {
     assignment_submissions (id) {
         id -> Int4,
@@ -18,6 +29,10 @@ diesel::table! {
         submit_at -> Nullable<Int8>,
         allow_rework -> Bool,
         test_duration -> Nullable<Int4>,
+        is_late -> Bool,
+        late_by -> Int8,
+        grade_held -> Bool,
+        anonymous_id -> Varchar,
     }
 }
 
@@ -33,6 +48,21 @@ diesel::table! {
         band_score_id -> Nullable<Int4>,
         grade_method -> Int4,
         grade_by_rubric_id -> Nullable<Uuid>,
+        open_at -> Nullable<Int8>,
+        close_at -> Nullable<Int8>,
+        due_at -> Nullable<Int8>,
+        late_policy -> Int4,
+        late_penalty -> Nullable<Float8>,
+        attempt_scoring_policy -> Int4,
+        hold_grades -> Bool,
+        release_grades_at -> Nullable<Int8>,
+        grades_released_at -> Nullable<Int8>,
+        anonymous_grading -> Bool,
+        double_marking -> Bool,
+        discrepancy_threshold -> Float8,
+        peer_review_count -> Int4,
+        peer_review_weight -> Float8,
+        peer_reviews_allocated_at -> Nullable<Int8>,
     }
 }
 
@@ -43,6 +73,10 @@ diesel::table! {
         range -> Jsonb,
         updated_at -> Int8,
         created_at -> Int8,
+        user_id -> Nullable<Int4>,
+        space_id -> Nullable<Int4>,
+        preset_key -> Nullable<Varchar>,
+        by_percentage -> Bool,
     }
 }
 
@@ -75,6 +109,36 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    feedback_annotations (id) {
+        id -> Uuid,
+        submission_id -> Int4,
+        page_content_id -> Uuid,
+        writing_block_id -> Nullable<Uuid>,
+        from_offset -> Int4,
+        to_offset -> Int4,
+        quoted_text -> Text,
+        comment -> Text,
+        creator_id -> Int4,
+        updated_at -> Int8,
+        created_at -> Int8,
+    }
+}
+
+diesel::table! {
+    file_comments (id) {
+        id -> Uuid,
+        submission_id -> Int4,
+        file_id -> Uuid,
+        start_time -> Float8,
+        end_time -> Nullable<Float8>,
+        comment -> Text,
+        creator_id -> Int4,
+        updated_at -> Int8,
+        created_at -> Int8,
+    }
+}
+
 diesel::table! {
     files (uuid) {
         uuid -> Uuid,
@@ -92,6 +156,27 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    mock_test_sections (mock_test_id, skill) {
+        mock_test_id -> Int4,
+        skill -> Int4,
+        document_id -> Uuid,
+        updated_at -> Int8,
+        created_at -> Int8,
+    }
+}
+
+diesel::table! {
+    mock_tests (id) {
+        id -> Int4,
+        space_id -> Int4,
+        creator_id -> Int4,
+        name -> Text,
+        updated_at -> Int8,
+        created_at -> Int8,
+    }
+}
+
 diesel::table! {
     notification_receivers (notification_id, user_id) {
         notification_id -> Uuid,
@@ -134,6 +219,58 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    peer_reviews (submission_id, reviewer_id) {
+        submission_id -> Int4,
+        reviewer_id -> Int4,
+        graded_data -> Nullable<Jsonb>,
+        score -> Nullable<Float8>,
+        submitted_at -> Nullable<Int8>,
+        updated_at -> Int8,
+        created_at -> Int8,
+    }
+}
+
+diesel::table! {
+    quiz_answer_keys (quiz_block_id) {
+        quiz_block_id -> Uuid,
+        answer -> Jsonb,
+        updated_at -> Int8,
+        created_at -> Int8,
+    }
+}
+
+diesel::table! {
+    quiz_blocks (id) {
+        id -> Uuid,
+        page_content_id -> Uuid,
+        creator_id -> Int4,
+        quiz_type -> Int4,
+        content -> Jsonb,
+        score -> Float8,
+        answer -> Nullable<Jsonb>,
+        original_quiz_block_id -> Nullable<Uuid>,
+        updated_at -> Int8,
+        created_at -> Int8,
+    }
+}
+
+diesel::table! {
+    regrade_requests (id) {
+        id -> Int4,
+        submission_id -> Int4,
+        requester_id -> Int4,
+        reason -> Text,
+        criterion -> Nullable<Text>,
+        status -> Int4,
+        responder_id -> Nullable<Int4>,
+        response -> Nullable<Text>,
+        responded_at -> Nullable<Int8>,
+        updated_at -> Int8,
+        created_at -> Int8,
+    }
+}
+
 diesel::table! {
     rubric_submissions (submission_id) {
         submission_id -> Int4,
@@ -141,6 +278,16 @@ diesel::table! {
         graded_data -> Jsonb,
         updated_at -> Int8,
         created_at -> Int8,
+        rubric_version -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    rubric_versions (rubric_id, version) {
+        rubric_id -> Uuid,
+        version -> Int4,
+        data -> Jsonb,
+        created_at -> Int8,
     }
 }
 
@@ -152,6 +299,8 @@ diesel::table! {
         updated_at -> Int8,
         created_at -> Int8,
         user_id -> Int4,
+        version -> Int4,
+        space_id -> Nullable<Int4>,
     }
 }
 
@@ -191,6 +340,41 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    submission_feedback_files (submission_id, file_id) {
+        submission_id -> Int4,
+        file_id -> Uuid,
+        creator_id -> Int4,
+        created_at -> Int8,
+    }
+}
+
+diesel::table! {
+    submission_grade_histories (id) {
+        id -> Int4,
+        submission_id -> Int4,
+        actor_id -> Nullable<Int4>,
+        source -> Int4,
+        old_final_grade -> Nullable<Float8>,
+        new_final_grade -> Nullable<Float8>,
+        old_feedback -> Nullable<Text>,
+        new_feedback -> Nullable<Text>,
+        created_at -> Int8,
+    }
+}
+
+diesel::table! {
+    submission_markings (submission_id, marker_id) {
+        submission_id -> Int4,
+        marker_id -> Int4,
+        grade -> Float8,
+        feedback -> Nullable<Text>,
+        graded_data -> Nullable<Jsonb>,
+        updated_at -> Int8,
+        created_at -> Int8,
+    }
+}
+
 diesel::table! {
     user_activities (user_id) {
         user_id -> Int4,
@@ -221,23 +405,46 @@ diesel::table! {
     }
 }
 
+diesel::joinable!(assignment_accommodations -> assignments (assignment_id));
+diesel::joinable!(assignment_accommodations -> users (user_id));
 diesel::joinable!(assignment_submissions -> assignments (assignment_id));
 diesel::joinable!(assignment_submissions -> documents (document_id));
 diesel::joinable!(assignment_submissions -> users (user_id));
 diesel::joinable!(assignments -> band_scores (band_score_id));
 diesel::joinable!(assignments -> documents (document_id));
 diesel::joinable!(assignments -> rubrics (grade_by_rubric_id));
+diesel::joinable!(band_scores -> spaces (space_id));
+diesel::joinable!(band_scores -> users (user_id));
 diesel::joinable!(document_assigned_users -> documents (document_id));
 diesel::joinable!(document_assigned_users -> users (assigned_user_id));
 diesel::joinable!(documents -> files (cover_photo_id));
 diesel::joinable!(documents -> spaces (space_id));
+diesel::joinable!(feedback_annotations -> assignment_submissions (submission_id));
+diesel::joinable!(feedback_annotations -> page_contents (page_content_id));
+diesel::joinable!(feedback_annotations -> users (creator_id));
+diesel::joinable!(feedback_annotations -> writing_blocks (writing_block_id));
+diesel::joinable!(file_comments -> assignment_submissions (submission_id));
+diesel::joinable!(file_comments -> files (file_id));
+diesel::joinable!(file_comments -> users (creator_id));
+diesel::joinable!(mock_test_sections -> documents (document_id));
+diesel::joinable!(mock_test_sections -> mock_tests (mock_test_id));
+diesel::joinable!(mock_tests -> spaces (space_id));
+diesel::joinable!(mock_tests -> users (creator_id));
 diesel::joinable!(notification_receivers -> notifications (notification_id));
 diesel::joinable!(notification_receivers -> users (user_id));
 diesel::joinable!(page_contents -> pages (page_id));
 diesel::joinable!(pages -> documents (document_id));
 diesel::joinable!(pages -> users (created_by_id));
+diesel::joinable!(peer_reviews -> assignment_submissions (submission_id));
+diesel::joinable!(peer_reviews -> users (reviewer_id));
+diesel::joinable!(quiz_answer_keys -> quiz_blocks (quiz_block_id));
+diesel::joinable!(quiz_blocks -> page_contents (page_content_id));
+diesel::joinable!(quiz_blocks -> users (creator_id));
+diesel::joinable!(regrade_requests -> assignment_submissions (submission_id));
 diesel::joinable!(rubric_submissions -> assignment_submissions (submission_id));
 diesel::joinable!(rubric_submissions -> rubrics (rubric_id));
+diesel::joinable!(rubric_versions -> rubrics (rubric_id));
+diesel::joinable!(rubrics -> spaces (space_id));
 diesel::joinable!(rubrics -> users (user_id));
 diesel::joinable!(space_invite_tokens -> spaces (space_id));
 diesel::joinable!(space_invite_tokens -> users (creator_id));
@@ -245,27 +452,47 @@ diesel::joinable!(space_members -> spaces (space_id));
 diesel::joinable!(space_members -> users (user_id));
 diesel::joinable!(spaces -> files (banner_id));
 diesel::joinable!(spaces -> users (creator_id));
+diesel::joinable!(submission_feedback_files -> assignment_submissions (submission_id));
+diesel::joinable!(submission_feedback_files -> files (file_id));
+diesel::joinable!(submission_feedback_files -> users (creator_id));
+diesel::joinable!(submission_grade_histories -> assignment_submissions (submission_id));
+diesel::joinable!(submission_grade_histories -> users (actor_id));
+diesel::joinable!(submission_markings -> assignment_submissions (submission_id));
+diesel::joinable!(submission_markings -> users (marker_id));
 diesel::joinable!(user_activities -> documents (last_document_id));
 diesel::joinable!(user_activities -> users (user_id));
 diesel::joinable!(writing_blocks -> page_contents (page_content_id));
 diesel::joinable!(writing_blocks -> users (creator_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    assignment_accommodations,
     assignment_submissions,
     assignments,
     band_scores,
     document_assigned_users,
     documents,
+    feedback_annotations,
+    file_comments,
     files,
+    mock_test_sections,
+    mock_tests,
     notification_receivers,
     notifications,
     page_contents,
     pages,
+    peer_reviews,
+    quiz_answer_keys,
+    quiz_blocks,
+    regrade_requests,
     rubric_submissions,
+    rubric_versions,
     rubrics,
     space_invite_tokens,
     space_members,
     spaces,
+    submission_feedback_files,
+    submission_grade_histories,
+    submission_markings,
     user_activities,
     users,
     writing_blocks,
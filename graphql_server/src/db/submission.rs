@@ -3,7 +3,8 @@ use diesel::{AsChangeset, ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl
 use uuid::Uuid;
 
 use super::schema::assignment_submissions;
-use crate::util::get_now_as_secs;
+use super::{Assignment, FeedbackAnnotation, SubmissionFeedbackFile};
+use crate::util::{generate_code, get_now_as_secs};
 
 #[derive(Debug, Clone, Insertable)]
 #[diesel(table_name = assignment_submissions)]
@@ -20,6 +21,9 @@ pub struct NewSubmission {
     pub allow_rework: bool,
     pub submit_at: Option<i64>,
     pub test_duration: Option<i32>,
+    pub is_late: bool,
+    pub late_by: i64,
+    pub anonymous_id: String,
 }
 
 impl From<Submission> for NewSubmission {
@@ -37,6 +41,9 @@ impl From<Submission> for NewSubmission {
             allow_rework: value.allow_rework,
             submit_at: value.submit_at,
             test_duration: value.test_duration,
+            is_late: value.is_late,
+            late_by: value.late_by,
+            anonymous_id: value.anonymous_id,
         }
     }
 }
@@ -68,6 +75,9 @@ impl NewSubmission {
             attempt_number,
             allow_rework,
             test_duration,
+            is_late: false,
+            late_by: 0,
+            anonymous_id: generate_code(),
         }
     }
 }
@@ -82,6 +92,8 @@ pub struct GradeSubmissionData {
     #[graphql(skip)]
     pub allow_for_student_view_answer: bool,
     #[graphql(skip)]
+    pub grade_held: bool,
+    #[graphql(skip)]
     pub updated_at: i64,
 }
 
@@ -97,9 +109,11 @@ pub enum SubmissionStatus {
 pub struct Submission {
     pub id: i32,
     pub assignment_id: i32,
+    #[graphql(skip_output)]
     pub user_id: i32,
     #[graphql(skip_output)]
     pub auto_grade: Option<f64>,
+    #[graphql(skip_output)]
     pub feedback: Option<String>,
     pub updated_at: i64,
     pub created_at: i64,
@@ -113,11 +127,36 @@ pub struct Submission {
     pub submit_at: Option<i64>,
     pub allow_rework: bool,
     pub test_duration: Option<i32>,
+    pub is_late: bool,
+    // Seconds after due time of assignment
+    pub late_by: i64,
+    // Graded but hidden from student until grades of assignment are released
+    pub grade_held: bool,
+    // Shown to graders instead of student while assignment is graded anonymously
+    pub anonymous_id: String,
 }
 
 impl Submission {
-    pub fn submission_status(&self) -> SubmissionStatus {
-        if self.feedback_at.is_some() {
+    pub fn pseudonym(&self) -> String {
+        format!("Anonymous {}", self.anonymous_id)
+    }
+
+    pub fn is_grade_released(&self) -> bool {
+        self.feedback_at.is_some() && !self.grade_held
+    }
+
+    // Graders only see pseudonym until grade of submission is released
+    pub fn is_anonymous(&self, assignment: &Assignment) -> bool {
+        assignment.anonymous_grading && !self.is_grade_released()
+    }
+
+    pub fn is_identity_hidden(&self, assignment: &Assignment, viewer_id: i32) -> bool {
+        self.user_id != viewer_id && self.is_anonymous(assignment)
+    }
+
+    // Student does not see held grade, so the submission is only submitted for them
+    pub fn submission_status(&self, include_held_grade: bool) -> SubmissionStatus {
+        if self.feedback_at.is_some() && (include_held_grade || !self.grade_held) {
             return SubmissionStatus::Graded;
         }
 
@@ -142,15 +181,32 @@ impl Submission {
         conn: &mut PgConnection,
         submission_id: i32,
         mut grade_data: GradeSubmissionData,
+        hold_grade: bool,
     ) -> Result<Self, Error> {
         grade_data.updated_at = get_now_as_secs();
         grade_data.feedback_at = Some(get_now_as_secs());
-        grade_data.allow_for_student_view_answer = true;
+        grade_data.allow_for_student_view_answer = !hold_grade;
+        grade_data.grade_held = hold_grade;
         diesel::update(assignment_submissions::table.find(submission_id))
             .set(grade_data)
             .get_result(conn)
     }
 
+    pub fn release_held_grades(
+        conn: &mut PgConnection,
+        assignment_id: i32,
+    ) -> Result<Vec<Self>, Error> {
+        diesel::update(assignment_submissions::table)
+            .filter(assignment_submissions::assignment_id.eq(assignment_id))
+            .filter(assignment_submissions::grade_held.eq(true))
+            .set((
+                assignment_submissions::grade_held.eq(false),
+                assignment_submissions::allow_for_student_view_answer.eq(true),
+                assignment_submissions::updated_at.eq(get_now_as_secs()),
+            ))
+            .get_results(conn)
+    }
+
     pub fn redo(conn: &mut PgConnection, submission_id: i32) -> Result<Self, Error> {
         diesel::update(assignment_submissions::table.find(submission_id))
             .set((
@@ -158,6 +214,9 @@ impl Submission {
                 assignment_submissions::submit_at.eq(None::<i64>),
                 assignment_submissions::feedback_at.eq(None::<i64>),
                 assignment_submissions::allow_for_student_view_answer.eq(false),
+                assignment_submissions::is_late.eq(false),
+                assignment_submissions::late_by.eq(0),
+                assignment_submissions::grade_held.eq(false),
                 assignment_submissions::updated_at.eq(get_now_as_secs()),
             ))
             .get_result(conn)
@@ -169,6 +228,18 @@ impl Submission {
                 assignment_submissions::allow_for_student_view_answer.eq(false),
                 assignment_submissions::allow_rework.eq(true),
                 assignment_submissions::feedback_at.eq(None::<i64>),
+                assignment_submissions::grade_held.eq(false),
+                assignment_submissions::updated_at.eq(get_now_as_secs()),
+            ))
+            .get_result(conn)
+    }
+
+    // Grade and feedback are kept so teacher can adjust them
+    pub fn return_to_grading(conn: &mut PgConnection, submission_id: i32) -> Result<Self, Error> {
+        diesel::update(assignment_submissions::table.find(submission_id))
+            .set((
+                assignment_submissions::feedback_at.eq(None::<i64>),
+                assignment_submissions::grade_held.eq(false),
                 assignment_submissions::updated_at.eq(get_now_as_secs()),
             ))
             .get_result(conn)
@@ -181,6 +252,9 @@ impl Submission {
         document_id: Uuid,
         allow_rework: bool,
     ) -> Result<Self, Error> {
+        // Annotations are anchored to content of previous document
+        FeedbackAnnotation::remove_all_by_submission(conn, submission_id)?;
+        SubmissionFeedbackFile::remove_all_by_submission(conn, submission_id)?;
         diesel::update(assignment_submissions::table.find(submission_id))
             .set((
                 assignment_submissions::attempt_number.eq(attempt_number),
@@ -193,43 +267,51 @@ impl Submission {
                 assignment_submissions::feedback_at.eq(None::<i64>),
                 assignment_submissions::allow_rework.eq(allow_rework),
                 assignment_submissions::allow_for_student_view_answer.eq(false),
+                assignment_submissions::is_late.eq(false),
+                assignment_submissions::late_by.eq(0),
+                assignment_submissions::grade_held.eq(false),
             ))
             .get_result(conn)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn submit(
         conn: &mut PgConnection,
         submission_id: i32,
         grade: f64,
         final_grade: f64,
         allow_for_student_view_answer: bool,
-    ) -> Result<(), Error> {
+        grade_held: bool,
+        submit_at: i64,
+        late_by: i64,
+    ) -> Result<Self, Error> {
         diesel::update(assignment_submissions::table.find(submission_id))
             .set((
                 assignment_submissions::auto_grade.eq(grade),
                 assignment_submissions::final_grade.eq(final_grade),
-                assignment_submissions::submit_at.eq(get_now_as_secs()),
+                assignment_submissions::submit_at.eq(submit_at),
+                assignment_submissions::is_late.eq(late_by > 0),
+                assignment_submissions::late_by.eq(late_by),
                 assignment_submissions::updated_at.eq(get_now_as_secs()),
                 assignment_submissions::allow_rework.eq(false),
                 assignment_submissions::allow_for_student_view_answer
                     .eq(allow_for_student_view_answer),
+                assignment_submissions::grade_held.eq(grade_held),
             ))
-            .execute(conn)?;
-        Ok(())
+            .get_result(conn)
     }
 
     pub fn update_final_grade(
         conn: &mut PgConnection,
         submission_id: i32,
         final_grade: f64,
-    ) -> Result<(), Error> {
+    ) -> Result<Self, Error> {
         diesel::update(assignment_submissions::table.find(submission_id))
             .set((
                 assignment_submissions::final_grade.eq(final_grade),
                 assignment_submissions::updated_at.eq(get_now_as_secs()),
             ))
-            .execute(conn)?;
-        Ok(())
+            .get_result(conn)
     }
 
     pub fn find_by_id(conn: &mut PgConnection, submission_id: i32) -> Result<Submission, Error> {
@@ -298,6 +380,16 @@ impl Submission {
             .get_results(conn)
     }
 
+    pub fn find_all_in_doing_by_assignment(
+        conn: &mut PgConnection,
+        assignment_id: i32,
+    ) -> Result<Vec<Self>, Error> {
+        assignment_submissions::table
+            .filter(assignment_submissions::assignment_id.eq(assignment_id))
+            .filter(assignment_submissions::submit_at.is_null())
+            .get_results(conn)
+    }
+
     pub fn find_all_by_assignments(
         conn: &mut PgConnection,
         assignment_ids: Vec<i32>,
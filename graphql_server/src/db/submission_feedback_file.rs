@@ -0,0 +1,70 @@
+use diesel::result::Error;
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
+use uuid::Uuid;
+
+use super::schema::submission_feedback_files;
+use crate::util::get_now_as_secs;
+
+// Audio or video recording of grader attached to feedback of submission
+#[derive(Debug, Clone, Insertable, Queryable, SimpleObject)]
+#[diesel(table_name = submission_feedback_files)]
+#[graphql(complex)]
+pub struct SubmissionFeedbackFile {
+    pub submission_id: i32,
+    pub file_id: Uuid,
+    pub creator_id: i32,
+    pub created_at: i64,
+}
+
+impl SubmissionFeedbackFile {
+    pub fn new(submission_id: i32, file_id: Uuid, creator_id: i32) -> Self {
+        Self {
+            submission_id,
+            file_id,
+            creator_id,
+            created_at: get_now_as_secs(),
+        }
+    }
+
+    pub fn upsert(conn: &mut PgConnection, item: Self) -> Result<Self, Error> {
+        diesel::insert_into(submission_feedback_files::table)
+            .values(&item)
+            .on_conflict((
+                submission_feedback_files::submission_id,
+                submission_feedback_files::file_id,
+            ))
+            .do_update()
+            .set(submission_feedback_files::creator_id.eq(item.creator_id))
+            .get_result(conn)
+    }
+
+    pub fn remove(
+        conn: &mut PgConnection,
+        submission_id: i32,
+        file_id: Uuid,
+    ) -> Result<usize, Error> {
+        diesel::delete(submission_feedback_files::table.find((submission_id, file_id)))
+            .execute(conn)
+    }
+
+    pub fn remove_all_by_submission(
+        conn: &mut PgConnection,
+        submission_id: i32,
+    ) -> Result<usize, Error> {
+        diesel::delete(
+            submission_feedback_files::table
+                .filter(submission_feedback_files::submission_id.eq(submission_id)),
+        )
+        .execute(conn)
+    }
+
+    pub fn find_all_by_submission(
+        conn: &mut PgConnection,
+        submission_id: i32,
+    ) -> Result<Vec<Self>, Error> {
+        submission_feedback_files::table
+            .filter(submission_feedback_files::submission_id.eq(submission_id))
+            .order(submission_feedback_files::created_at.asc())
+            .get_results(conn)
+    }
+}
@@ -0,0 +1,95 @@
+use diesel::result::Error;
+use diesel::sql_types::Integer;
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
+
+use super::schema::submission_grade_histories;
+use super::Submission;
+use crate::impl_enum_for_db;
+use crate::util::get_now_as_secs;
+
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, FromPrimitive, ToPrimitive, AsExpression, FromSqlRow, Enum,
+)]
+#[diesel(sql_type = Integer)]
+pub enum GradeChangeSource {
+    // Auto grade when student submits or assignment closes
+    Submit,
+    Grade,
+    Rubric,
+    // Agreed grade of double marked submission
+    Moderation,
+}
+
+impl_enum_for_db!(GradeChangeSource);
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = submission_grade_histories)]
+pub struct NewSubmissionGradeHistory {
+    pub submission_id: i32,
+    pub actor_id: Option<i32>,
+    pub source: GradeChangeSource,
+    pub old_final_grade: Option<f64>,
+    pub new_final_grade: Option<f64>,
+    pub old_feedback: Option<String>,
+    pub new_feedback: Option<String>,
+    pub created_at: i64,
+}
+
+// Append only, a row is never updated or removed
+#[derive(Debug, Clone, Queryable, SimpleObject)]
+#[graphql(complex)]
+pub struct SubmissionGradeHistory {
+    pub id: i32,
+    pub submission_id: i32,
+    // None if grade is changed by system
+    pub actor_id: Option<i32>,
+    pub source: GradeChangeSource,
+    pub old_final_grade: Option<f64>,
+    pub new_final_grade: Option<f64>,
+    pub old_feedback: Option<String>,
+    pub new_feedback: Option<String>,
+    pub created_at: i64,
+}
+
+impl SubmissionGradeHistory {
+    // Only insert history if final grade or feedback is changed
+    pub fn record(
+        conn: &mut PgConnection,
+        before: &Submission,
+        after: &Submission,
+        actor_id: Option<i32>,
+        source: GradeChangeSource,
+    ) -> Result<Option<Self>, Error> {
+        if before.final_grade == after.final_grade && before.feedback == after.feedback {
+            return Ok(None);
+        }
+
+        let item = NewSubmissionGradeHistory {
+            submission_id: after.id,
+            actor_id,
+            source,
+            old_final_grade: before.final_grade,
+            new_final_grade: after.final_grade,
+            old_feedback: before.feedback.clone(),
+            new_feedback: after.feedback.clone(),
+            created_at: get_now_as_secs(),
+        };
+        let history = diesel::insert_into(submission_grade_histories::table)
+            .values(item)
+            .get_result(conn)?;
+        Ok(Some(history))
+    }
+
+    pub fn find_all_by_submission(
+        conn: &mut PgConnection,
+        submission_id: i32,
+    ) -> Result<Vec<Self>, Error> {
+        submission_grade_histories::table
+            .filter(submission_grade_histories::submission_id.eq(submission_id))
+            .order((
+                submission_grade_histories::created_at.desc(),
+                submission_grade_histories::id.desc(),
+            ))
+            .get_results(conn)
+    }
+}
@@ -0,0 +1,85 @@
+use diesel::result::Error;
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
+
+use super::schema::submission_markings;
+use super::RubricTableData;
+use crate::util::get_now_as_secs;
+
+// Independent grading of a submission by one marker, hidden from student
+#[derive(Debug, Clone, Insertable, Queryable, InputObject, SimpleObject)]
+#[diesel(table_name = submission_markings)]
+#[graphql(complex, input_name = "SubmissionMarkingInput")]
+pub struct SubmissionMarking {
+    pub submission_id: i32,
+    #[graphql(skip_input)]
+    pub marker_id: i32,
+    pub grade: f64,
+    pub feedback: Option<String>,
+    // Graded rubric if assignment is graded by rubric, grade is total score of it
+    pub graded_data: Option<RubricTableData>,
+    #[graphql(skip_input)]
+    pub updated_at: i64,
+    #[graphql(skip_input)]
+    pub created_at: i64,
+}
+
+impl SubmissionMarking {
+    pub fn upsert(conn: &mut PgConnection, mut item: Self) -> Result<Self, Error> {
+        item.updated_at = get_now_as_secs();
+        item.created_at = get_now_as_secs();
+        diesel::insert_into(submission_markings::table)
+            .values(&item)
+            .on_conflict((
+                submission_markings::submission_id,
+                submission_markings::marker_id,
+            ))
+            .do_update()
+            .set((
+                submission_markings::grade.eq(&item.grade),
+                submission_markings::feedback.eq(&item.feedback),
+                submission_markings::graded_data.eq(&item.graded_data),
+                submission_markings::updated_at.eq(&item.updated_at),
+            ))
+            .get_result(conn)
+    }
+
+    pub fn remove(
+        conn: &mut PgConnection,
+        submission_id: i32,
+        marker_id: i32,
+    ) -> Result<usize, Error> {
+        diesel::delete(submission_markings::table.find((submission_id, marker_id))).execute(conn)
+    }
+
+    pub fn find_all_by_submission(
+        conn: &mut PgConnection,
+        submission_id: i32,
+    ) -> Result<Vec<Self>, Error> {
+        submission_markings::table
+            .filter(submission_markings::submission_id.eq(submission_id))
+            .order(submission_markings::created_at.asc())
+            .get_results(conn)
+    }
+
+    pub fn find_all_by_submissions(
+        conn: &mut PgConnection,
+        submission_ids: &[i32],
+    ) -> Result<Vec<Self>, Error> {
+        submission_markings::table
+            .filter(submission_markings::submission_id.eq_any(submission_ids))
+            .order(submission_markings::created_at.asc())
+            .get_results(conn)
+    }
+
+    // Difference between highest and lowest grade, None if there are less than two markings
+    pub fn spread(markings: &[Self]) -> Option<f64> {
+        if markings.len() < 2 {
+            return None;
+        }
+
+        let grades = markings.iter().map(|marking| marking.grade);
+        let highest = grades.clone().fold(f64::MIN, f64::max);
+        let lowest = grades.fold(f64::MAX, f64::min);
+        Some(highest - lowest)
+    }
+}
@@ -6,13 +6,13 @@ use super::schema::writing_blocks;
 use crate::util::get_now_as_secs;
 
 #[derive(Debug, Clone, Insertable, Queryable, SimpleObject, InputObject)]
-#[graphql(input_name = "WritingBlockInput")]
+#[graphql(input_name = "WritingBlockInput", complex)]
 #[diesel(table_name = writing_blocks)]
 pub struct WritingBlock {
     pub id: Uuid,
     #[graphql(skip_input)]
     pub page_content_id: Uuid,
-    #[graphql(skip_input)]
+    #[graphql(skip)]
     pub creator_id: i32,
     pub content: serde_json::Value,
     #[graphql(skip_input)]
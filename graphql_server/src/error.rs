@@ -194,3 +194,17 @@ impl From<std::io::Error> for IkigaiError {
         Self::InternalServerError
     }
 }
+
+impl From<csv::Error> for IkigaiError {
+    fn from(e: csv::Error) -> Self {
+        error!("CSV Error: {:?}", e);
+        Self::InternalServerError
+    }
+}
+
+impl From<rust_xlsxwriter::XlsxError> for IkigaiError {
+    fn from(e: rust_xlsxwriter::XlsxError) -> Self {
+        error!("XLSX Error: {:?}", e);
+        Self::InternalServerError
+    }
+}
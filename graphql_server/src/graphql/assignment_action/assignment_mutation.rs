@@ -2,14 +2,22 @@ use aj::AJ;
 use aj::{JobBuilder, JobType};
 use async_graphql::*;
 use diesel::Connection;
+use std::collections::HashMap;
+use uuid::Uuid;
 
-use crate::authorization::DocumentActionPermission;
+use crate::authorization::{
+    BandScoreActionPermission, DocumentActionPermission, RubricActionPermission,
+    SpaceActionPermission,
+};
+use crate::background_job::assignment_job::{CloseAssignment, ReleaseGrades};
+use crate::background_job::storage_job::add_generate_waveform_job;
 use crate::background_job::submission_job::CompleteSubmission;
 use crate::db::*;
 use crate::error::{IkigaiError, IkigaiErrorExt};
+use crate::graphql::validator::ValidBandScore;
 use crate::helper::*;
 use crate::notification_center::send_notification;
-use crate::util::{get_date_from_ts, get_now_as_secs};
+use crate::util::{generate_code, get_date_from_ts, get_now_as_secs};
 
 #[derive(Default)]
 pub struct AssignmentMutation;
@@ -30,7 +38,87 @@ impl AssignmentMutation {
             DocumentActionPermission::ManageDocument,
         )
         .await?;
-        Assignment::update(&mut conn, assignment_id, data).format_err()?;
+        if let (Some(open_at), Some(close_at)) = (data.open_at, data.close_at) {
+            if open_at >= close_at {
+                return Err(IkigaiError::new_bad_request(
+                    "Close time must be after open time",
+                ))
+                .format_err();
+            }
+        }
+        if let (Some(due_at), Some(close_at)) = (data.due_at, data.close_at) {
+            if due_at > close_at {
+                return Err(IkigaiError::new_bad_request(
+                    "Due time cannot be after close time",
+                ))
+                .format_err();
+            }
+        }
+        let is_valid_late_penalty = match data.late_policy {
+            LatePolicy::None => true,
+            LatePolicy::PercentagePerDay => data
+                .late_penalty
+                .is_some_and(|late_penalty| (0.0..=100.0).contains(&late_penalty)),
+            LatePolicy::Cap => data
+                .late_penalty
+                .is_some_and(|late_penalty| late_penalty >= 0.0),
+        };
+        if !is_valid_late_penalty {
+            return Err(IkigaiError::new_bad_request(
+                "Late penalty is required, percentage must be from 0 to 100 and cap cannot be negative",
+            ))
+            .format_err();
+        }
+        if data.peer_review_count < 0 || !(0.0..=1.0).contains(&data.peer_review_weight) {
+            return Err(IkigaiError::new_bad_request(
+                "Peer review count cannot be negative and its weight must be from 0 to 1",
+            ))
+            .format_err();
+        }
+        if data.peer_review_count > 0 && data.grade_by_rubric_id.is_none() {
+            return Err(IkigaiError::new_bad_request(
+                "Peer review needs a rubric to grade by",
+            ))
+            .format_err();
+        }
+        if data.peer_review_count > 0 && data.close_at.is_none() && data.due_at.is_none() {
+            return Err(IkigaiError::new_bad_request(
+                "Peer review needs a close time or due time",
+            ))
+            .format_err();
+        }
+        if let Some(band_score_id) = data.band_score_id {
+            if assignment.band_score_id != Some(band_score_id) {
+                band_score_quick_authorize(
+                    ctx,
+                    band_score_id,
+                    BandScoreActionPermission::ViewBandScore,
+                )
+                .await?;
+            }
+        }
+        if let Some(rubric_id) = data.grade_by_rubric_id {
+            if assignment.grade_by_rubric_id != Some(rubric_id) {
+                rubric_quick_authorize(ctx, rubric_id, RubricActionPermission::UseRubric).await?;
+            }
+        }
+        let updated_assignment = Assignment::update(&mut conn, assignment_id, data).format_err()?;
+
+        if let Some(close_at) = updated_assignment.close_at {
+            // Close time in the past closes the assignment right away
+            if assignment.close_at != Some(close_at) {
+                let message = CloseAssignment {
+                    assignment_id,
+                    close_at,
+                };
+                let run_at = close_at.max(get_now_as_secs());
+                let job = JobBuilder::default()
+                    .message(message)
+                    .job_type(JobType::ScheduledAt(get_date_from_ts(run_at)))
+                    .build()?;
+                AJ::add_job(job);
+            }
+        }
 
         Ok(true)
     }
@@ -93,13 +181,19 @@ impl AssignmentMutation {
             DocumentActionPermission::ViewDocument,
         )
         .await?;
+        check_assignment_availability(&assignment).format_err()?;
+
+        let accommodation =
+            AssignmentAccommodation::find_opt(&mut conn, assignment_id, user_id).format_err()?;
+        let test_duration = assignment.test_duration_for(accommodation.as_ref());
 
         // Check attempt time
         let last_submission =
             Submission::find_last_submission(&mut conn, user_id, assignment_id).format_err()?;
-        if let (Some(last_submission), Some(max_number_of_attempt)) =
-            (&last_submission, assignment.max_number_of_attempt)
-        {
+        if let (Some(last_submission), Some(max_number_of_attempt)) = (
+            &last_submission,
+            assignment.max_number_of_attempt_for(accommodation.as_ref()),
+        ) {
             if last_submission.attempt_number + 1 > max_number_of_attempt {
                 return Err(IkigaiError::new_bad_request(
                     "You reach max attempt times for this assignment!",
@@ -120,6 +214,11 @@ impl AssignmentMutation {
         let assignment_document =
             Document::find_by_id(&mut conn, assignment.document_id).format_err()?;
 
+        // Student keeps the same pseudonym across attempts
+        let anonymous_id = last_submission
+            .as_ref()
+            .map_or_else(generate_code, |s| s.anonymous_id.clone());
+
         let submission = conn
             .transaction::<_, IkigaiError, _>(|conn| {
                 let config = DocumentCloneConfigBuilder::default()
@@ -128,18 +227,20 @@ impl AssignmentMutation {
                     .clone_to_space(assignment_document.space_id)
                     .clone_children(false)
                     .keep_document_type(false)
+                    .keep_answer_keys(false)
                     .build()
                     .unwrap();
                 let document = assignment_document.deep_clone(conn, config)?;
 
-                let new_submission = NewSubmission::new(
+                let mut new_submission = NewSubmission::new(
                     user_id,
                     assignment_id,
                     document.id,
                     last_submission.map_or_else(|| 1, |s| s.attempt_number + 1),
-                    assignment.test_duration.is_none(),
-                    assignment.test_duration,
+                    test_duration.is_none(),
+                    test_duration,
                 );
+                new_submission.anonymous_id = anonymous_id;
                 let submission = Submission::insert(conn, new_submission)?;
 
                 try_add_rubric_submission(conn, &assignment, &submission)?;
@@ -185,6 +286,7 @@ impl AssignmentMutation {
         if submission.submit_at.is_some() {
             return Err(IkigaiError::new_bad_request("Cannot submit twice")).format_err()?;
         }
+        check_assignment_availability(&assignment).format_err()?;
 
         submit_submission(&mut conn, &submission, &assignment, false).format_err()?;
 
@@ -194,7 +296,11 @@ impl AssignmentMutation {
             Notification::new_submit_submission_notification(SubmitSubmissionContext {
                 document_submission_id: submission.document_id,
                 submission_name: assignment_document.title,
-                student_name: user.name(),
+                student_name: if assignment.anonymous_grading {
+                    submission.pseudonym()
+                } else {
+                    user.name()
+                },
             });
         let notification = Notification::insert(&mut conn, notification).format_err()?;
         let space_members = SpaceMember::find_all_space_members_by_role_and_class(
@@ -228,17 +334,16 @@ impl AssignmentMutation {
             DocumentActionPermission::ManageDocument,
         )
         .await?;
-        Submission::grade_submission(&mut conn, submission_id, grade_data).format_err()?;
-
-        let submission_document =
-            Document::find_by_id(&mut conn, submission.document_id).format_err()?;
-        let notification =
-            Notification::new_feedback_submission_notification(FeedbackSubmissionContext {
-                document_submission_id: submission.document_id,
-                submission_name: submission_document.title,
-            });
-        let notification = Notification::insert(&mut conn, notification).format_err()?;
-        send_notification(&mut conn, notification, vec![submission.user_id]).format_err()?;
+        let user_id = get_user_id_from_ctx(ctx).await?;
+        grade_submission(
+            &mut conn,
+            &submission,
+            &assignment,
+            grade_data,
+            user_id,
+            GradeChangeSource::Grade,
+        )
+        .format_err()?;
 
         Ok(true)
     }
@@ -259,15 +364,776 @@ impl AssignmentMutation {
         )
         .await?;
 
-        let final_grade = data.graded_data.total_rubric_score();
+        // Structure stays with the version copied when submission started
+        let user_id = get_user_id_from_ctx(ctx).await?;
         let item = conn
             .transaction::<_, IkigaiError, _>(|conn| {
-                Submission::update_final_grade(conn, submission.id, final_grade)?;
-                let item = RubricSubmission::upsert(conn, data)?;
-                Ok(item)
+                grade_rubric_submission(conn, &submission, &assignment, data, user_id)
             })
             .format_err()?;
 
         Ok(item)
     }
+
+    async fn assignment_bulk_request_redo(
+        &self,
+        ctx: &Context<'_>,
+        submission_ids: Vec<i32>,
+    ) -> Result<bool> {
+        let (submissions, _) = authorize_submissions_to_grade(ctx, &submission_ids).await?;
+
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        conn.transaction::<_, IkigaiError, _>(|conn| {
+            for submission in &submissions {
+                Submission::request_redo(conn, submission.id)?;
+            }
+            Ok(())
+        })
+        .format_err()?;
+
+        Ok(true)
+    }
+
+    async fn assignment_bulk_grade_submissions(
+        &self,
+        ctx: &Context<'_>,
+        submission_ids: Vec<i32>,
+        grade_data: GradeSubmissionData,
+    ) -> Result<bool> {
+        let (submissions, assignments) =
+            authorize_submissions_to_grade(ctx, &submission_ids).await?;
+
+        let user_id = get_user_id_from_ctx(ctx).await?;
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        bulk_grade_submissions(&mut conn, &submissions, &assignments, grade_data, user_id)
+            .format_err()?;
+
+        Ok(true)
+    }
+
+    async fn assignment_bulk_update_rubric_submissions(
+        &self,
+        ctx: &Context<'_>,
+        data: Vec<RubricSubmission>,
+    ) -> Result<Vec<RubricSubmission>> {
+        let submission_ids: Vec<i32> = data.iter().map(|item| item.submission_id).collect();
+        let (submissions, assignments) =
+            authorize_submissions_to_grade(ctx, &submission_ids).await?;
+        if submissions.len() != data.len() {
+            return Err(IkigaiError::new_bad_request(
+                "Submission is graded more than once",
+            ))
+            .format_err();
+        }
+        let submissions: HashMap<i32, Submission> = submissions
+            .into_iter()
+            .map(|submission| (submission.id, submission))
+            .collect();
+
+        let user_id = get_user_id_from_ctx(ctx).await?;
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let items = conn
+            .transaction::<_, IkigaiError, _>(|conn| {
+                let mut items = vec![];
+                for item in data {
+                    let submission = &submissions[&item.submission_id];
+                    let assignment = &assignments[&submission.assignment_id];
+                    items.push(grade_rubric_submission(
+                        conn, submission, assignment, item, user_id,
+                    )?);
+                }
+                Ok(items)
+            })
+            .format_err()?;
+
+        Ok(items)
+    }
+
+    async fn assignment_upsert_marking(
+        &self,
+        ctx: &Context<'_>,
+        mut data: SubmissionMarking,
+    ) -> Result<SubmissionMarking> {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let submission = Submission::find_by_id(&mut conn, data.submission_id).format_err()?;
+        let assignment =
+            Assignment::find_by_id(&mut conn, submission.assignment_id).format_err()?;
+        document_quick_authorize(
+            ctx,
+            assignment.document_id,
+            DocumentActionPermission::ManageDocument,
+        )
+        .await?;
+
+        data.marker_id = get_user_id_from_ctx(ctx).await?;
+        let marking = upsert_marking(&mut conn, &submission, &assignment, data).format_err()?;
+        Ok(marking)
+    }
+
+    async fn assignment_remove_marking(
+        &self,
+        ctx: &Context<'_>,
+        submission_id: i32,
+    ) -> Result<bool> {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let submission = Submission::find_by_id(&mut conn, submission_id).format_err()?;
+        let assignment =
+            Assignment::find_by_id(&mut conn, submission.assignment_id).format_err()?;
+        document_quick_authorize(
+            ctx,
+            assignment.document_id,
+            DocumentActionPermission::ManageDocument,
+        )
+        .await?;
+
+        let user_id = get_user_id_from_ctx(ctx).await?;
+        SubmissionMarking::remove(&mut conn, submission_id, user_id).format_err()?;
+        Ok(true)
+    }
+
+    async fn assignment_moderate_submission(
+        &self,
+        ctx: &Context<'_>,
+        submission_id: i32,
+        grade_data: GradeSubmissionData,
+    ) -> Result<bool> {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let submission = Submission::find_by_id(&mut conn, submission_id).format_err()?;
+        let assignment =
+            Assignment::find_by_id(&mut conn, submission.assignment_id).format_err()?;
+        document_quick_authorize(
+            ctx,
+            assignment.document_id,
+            DocumentActionPermission::ManageDocument,
+        )
+        .await?;
+
+        if !assignment.double_marking {
+            return Err(IkigaiError::new_bad_request(
+                "Assignment is not double marked",
+            ))
+            .format_err();
+        }
+        let markings =
+            SubmissionMarking::find_all_by_submission(&mut conn, submission_id).format_err()?;
+        if markings.len() < 2 {
+            return Err(IkigaiError::new_bad_request(
+                "Submission needs at least two markings before moderation",
+            ))
+            .format_err();
+        }
+
+        let user_id = get_user_id_from_ctx(ctx).await?;
+        grade_submission(
+            &mut conn,
+            &submission,
+            &assignment,
+            grade_data,
+            user_id,
+            GradeChangeSource::Moderation,
+        )
+        .format_err()?;
+
+        Ok(true)
+    }
+
+    async fn assignment_allocate_peer_reviews(
+        &self,
+        ctx: &Context<'_>,
+        assignment_id: i32,
+    ) -> Result<i32> {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let assignment = Assignment::find_by_id(&mut conn, assignment_id).format_err()?;
+        document_quick_authorize(
+            ctx,
+            assignment.document_id,
+            DocumentActionPermission::ManageDocument,
+        )
+        .await?;
+        // Students submitting after allocation would never be reviewed
+        let is_after_deadline = assignment
+            .close_at
+            .or(assignment.due_at)
+            .is_some_and(|deadline| deadline <= get_now_as_secs());
+        if !is_after_deadline {
+            return Err(IkigaiError::new_bad_request(
+                "Peer reviews can only be allocated after the deadline",
+            ))
+            .format_err();
+        }
+
+        let count = allocate_peer_reviews(&mut conn, &assignment).format_err()?;
+        Ok(count as i32)
+    }
+
+    async fn assignment_submit_peer_review(
+        &self,
+        ctx: &Context<'_>,
+        submission_id: i32,
+        graded_data: RubricTableData,
+    ) -> Result<PeerReview> {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let submission = Submission::find_by_id(&mut conn, submission_id).format_err()?;
+        let assignment =
+            Assignment::find_by_id(&mut conn, submission.assignment_id).format_err()?;
+        document_quick_authorize(
+            ctx,
+            submission.document_id,
+            DocumentActionPermission::PeerReviewDocument,
+        )
+        .await?;
+
+        let user_id = get_user_id_from_ctx(ctx).await?;
+        let review = submit_peer_review(&mut conn, &submission, &assignment, user_id, graded_data)
+            .format_err()?;
+        Ok(review)
+    }
+
+    async fn assignment_request_regrade(
+        &self,
+        ctx: &Context<'_>,
+        submission_id: i32,
+        reason: String,
+        criterion: Option<String>,
+    ) -> Result<RegradeRequest> {
+        let user = get_user_from_ctx(ctx).await?;
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let submission = Submission::find_by_id(&mut conn, submission_id).format_err()?;
+        let assignment =
+            Assignment::find_by_id(&mut conn, submission.assignment_id).format_err()?;
+        document_quick_authorize(
+            ctx,
+            submission.document_id,
+            DocumentActionPermission::ViewDocument,
+        )
+        .await?;
+
+        if submission.user_id != user.id {
+            return Err(IkigaiError::new_bad_request(
+                "Incorrect owner of submission",
+            ))
+            .format_err();
+        }
+
+        let request = request_regrade(
+            &mut conn,
+            &submission,
+            &assignment,
+            &user,
+            reason,
+            criterion,
+        )
+        .format_err()?;
+        Ok(request)
+    }
+
+    async fn assignment_accept_regrade_request(
+        &self,
+        ctx: &Context<'_>,
+        regrade_request_id: i32,
+        response: Option<String>,
+    ) -> Result<RegradeRequest> {
+        let request = authorize_pending_regrade_request(ctx, regrade_request_id).await?;
+
+        let user_id = get_user_id_from_ctx(ctx).await?;
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let request = conn
+            .transaction::<_, IkigaiError, _>(|conn| {
+                Submission::return_to_grading(conn, request.submission_id)?;
+                let request = RegradeRequest::respond(
+                    conn,
+                    request.id,
+                    RegradeRequestStatus::Accepted,
+                    user_id,
+                    response,
+                )?;
+                Ok(request)
+            })
+            .format_err()?;
+
+        Ok(request)
+    }
+
+    async fn assignment_reject_regrade_request(
+        &self,
+        ctx: &Context<'_>,
+        regrade_request_id: i32,
+        response: String,
+    ) -> Result<RegradeRequest> {
+        let request = authorize_pending_regrade_request(ctx, regrade_request_id).await?;
+
+        let user_id = get_user_id_from_ctx(ctx).await?;
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let request = RegradeRequest::respond(
+            &mut conn,
+            request.id,
+            RegradeRequestStatus::Rejected,
+            user_id,
+            Some(response),
+        )
+        .format_err()?;
+
+        Ok(request)
+    }
+
+    async fn assignment_add_feedback_annotation(
+        &self,
+        ctx: &Context<'_>,
+        mut data: FeedbackAnnotation,
+    ) -> Result<FeedbackAnnotation> {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let submission = Submission::find_by_id(&mut conn, data.submission_id).format_err()?;
+        let assignment =
+            Assignment::find_by_id(&mut conn, submission.assignment_id).format_err()?;
+        document_quick_authorize(
+            ctx,
+            assignment.document_id,
+            DocumentActionPermission::ManageDocument,
+        )
+        .await?;
+        check_annotation_anchor(&mut conn, &submission, &data).format_err()?;
+
+        data.id = Uuid::new_v4();
+        data.creator_id = get_user_id_from_ctx(ctx).await?;
+        let annotation = FeedbackAnnotation::insert(&mut conn, data).format_err()?;
+        Ok(annotation)
+    }
+
+    async fn assignment_update_feedback_annotation(
+        &self,
+        ctx: &Context<'_>,
+        annotation_id: Uuid,
+        comment: String,
+    ) -> Result<FeedbackAnnotation> {
+        authorize_feedback_annotation(ctx, annotation_id).await?;
+        if comment.trim().is_empty() {
+            return Err(IkigaiError::new_bad_request("Comment cannot be empty")).format_err();
+        }
+
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let annotation =
+            FeedbackAnnotation::update_comment(&mut conn, annotation_id, comment).format_err()?;
+        Ok(annotation)
+    }
+
+    async fn assignment_remove_feedback_annotation(
+        &self,
+        ctx: &Context<'_>,
+        annotation_id: Uuid,
+    ) -> Result<bool> {
+        authorize_feedback_annotation(ctx, annotation_id).await?;
+
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        FeedbackAnnotation::remove(&mut conn, annotation_id).format_err()?;
+        Ok(true)
+    }
+
+    async fn assignment_add_file_comment(
+        &self,
+        ctx: &Context<'_>,
+        mut data: FileComment,
+    ) -> Result<FileComment> {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let submission = Submission::find_by_id(&mut conn, data.submission_id).format_err()?;
+        let assignment =
+            Assignment::find_by_id(&mut conn, submission.assignment_id).format_err()?;
+        document_quick_authorize(
+            ctx,
+            assignment.document_id,
+            DocumentActionPermission::ManageDocument,
+        )
+        .await?;
+        check_file_comment(&mut conn, &submission, &data).format_err()?;
+
+        data.id = Uuid::new_v4();
+        data.creator_id = get_user_id_from_ctx(ctx).await?;
+        let file_comment = FileComment::insert(&mut conn, data).format_err()?;
+        Ok(file_comment)
+    }
+
+    async fn assignment_update_file_comment(
+        &self,
+        ctx: &Context<'_>,
+        file_comment_id: Uuid,
+        comment: String,
+    ) -> Result<FileComment> {
+        authorize_file_comment(ctx, file_comment_id).await?;
+        if comment.trim().is_empty() {
+            return Err(IkigaiError::new_bad_request("Comment cannot be empty")).format_err();
+        }
+
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let file_comment =
+            FileComment::update_comment(&mut conn, file_comment_id, comment).format_err()?;
+        Ok(file_comment)
+    }
+
+    async fn assignment_remove_file_comment(
+        &self,
+        ctx: &Context<'_>,
+        file_comment_id: Uuid,
+    ) -> Result<bool> {
+        authorize_file_comment(ctx, file_comment_id).await?;
+
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        FileComment::remove(&mut conn, file_comment_id).format_err()?;
+        Ok(true)
+    }
+
+    async fn assignment_add_feedback_file(
+        &self,
+        ctx: &Context<'_>,
+        submission_id: i32,
+        file_id: Uuid,
+    ) -> Result<SubmissionFeedbackFile> {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let submission = Submission::find_by_id(&mut conn, submission_id).format_err()?;
+        let assignment =
+            Assignment::find_by_id(&mut conn, submission.assignment_id).format_err()?;
+        document_quick_authorize(
+            ctx,
+            assignment.document_id,
+            DocumentActionPermission::ManageDocument,
+        )
+        .await?;
+
+        let user_id = get_user_id_from_ctx(ctx).await?;
+        let file = user_is_owner_of_file(&mut conn, user_id, file_id)?;
+        if !file.content_type.starts_with("audio/") && !file.content_type.starts_with("video/") {
+            return Err(IkigaiError::new_bad_request(
+                "Feedback file must be an audio or video",
+            ))
+            .format_err();
+        }
+
+        let feedback_file = SubmissionFeedbackFile::upsert(
+            &mut conn,
+            SubmissionFeedbackFile::new(submission.id, file.uuid, user_id),
+        )
+        .format_err()?;
+        if file.content_type == "audio/mpeg" && file.waveform_audio_json_str.is_none() {
+            add_generate_waveform_job(file.uuid);
+        }
+
+        Ok(feedback_file)
+    }
+
+    async fn assignment_remove_feedback_file(
+        &self,
+        ctx: &Context<'_>,
+        submission_id: i32,
+        file_id: Uuid,
+    ) -> Result<bool> {
+        let assignment = {
+            let mut conn = get_conn_from_ctx(ctx).await?;
+            let submission = Submission::find_by_id(&mut conn, submission_id).format_err()?;
+            Assignment::find_by_id(&mut conn, submission.assignment_id).format_err()?
+        };
+        document_quick_authorize(
+            ctx,
+            assignment.document_id,
+            DocumentActionPermission::ManageDocument,
+        )
+        .await?;
+
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        SubmissionFeedbackFile::remove(&mut conn, submission_id, file_id).format_err()?;
+        Ok(true)
+    }
+
+    async fn assignment_upsert_accommodation(
+        &self,
+        ctx: &Context<'_>,
+        data: AssignmentAccommodation,
+    ) -> Result<AssignmentAccommodation> {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let assignment = Assignment::find_by_id(&mut conn, data.assignment_id).format_err()?;
+        document_quick_authorize(
+            ctx,
+            assignment.document_id,
+            DocumentActionPermission::ManageDocument,
+        )
+        .await?;
+        if data
+            .test_duration
+            .is_some_and(|test_duration| test_duration <= 0)
+            || data
+                .max_number_of_attempt
+                .is_some_and(|max_number_of_attempt| max_number_of_attempt <= 0)
+        {
+            return Err(IkigaiError::new_bad_request(
+                "Test duration and number of attempts must be positive",
+            ))
+            .format_err();
+        }
+        let assignment_document =
+            Document::find_by_id(&mut conn, assignment.document_id).format_err()?;
+        let member = if let Some(space_id) = assignment_document.space_id {
+            SpaceMember::find_opt(&mut conn, space_id, data.user_id).format_err()?
+        } else {
+            None
+        };
+        if !member.is_some_and(|member| member.role == Role::Student) {
+            return Err(IkigaiError::new_bad_request(
+                "Accommodation is only for students of the space",
+            ))
+            .format_err();
+        }
+
+        let accommodation = AssignmentAccommodation::upsert(&mut conn, data).format_err()?;
+        Ok(accommodation)
+    }
+
+    async fn assignment_remove_accommodation(
+        &self,
+        ctx: &Context<'_>,
+        assignment_id: i32,
+        user_id: i32,
+    ) -> Result<bool> {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let assignment = Assignment::find_by_id(&mut conn, assignment_id).format_err()?;
+        document_quick_authorize(
+            ctx,
+            assignment.document_id,
+            DocumentActionPermission::ManageDocument,
+        )
+        .await?;
+
+        AssignmentAccommodation::remove(&mut conn, assignment_id, user_id).format_err()?;
+        Ok(true)
+    }
+
+    async fn assignment_export_submissions(
+        &self,
+        ctx: &Context<'_>,
+        assignment_id: i32,
+        format: ExportFormat,
+    ) -> Result<Option<String>> {
+        let user_id = get_user_id_from_ctx(ctx).await?;
+        let (assignment, document) = {
+            let mut conn = get_conn_from_ctx(ctx).await?;
+            let assignment = Assignment::find_by_id(&mut conn, assignment_id).format_err()?;
+            let document = Document::find_by_id(&mut conn, assignment.document_id).format_err()?;
+            (assignment, document)
+        };
+        document_quick_authorize(
+            ctx,
+            assignment.document_id,
+            DocumentActionPermission::ManageDocument,
+        )
+        .await?;
+
+        let data = {
+            let mut conn = get_conn_from_ctx(ctx).await?;
+            build_assignment_export(&mut conn, &assignment)
+                .and_then(|table| table.to_bytes(format))
+                .format_err()?
+        };
+        upload_export_file(ctx, user_id, &document.title, format, data).await
+    }
+
+    async fn assignment_create_band_score(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(validator(custom = "ValidBandScore"))] mut band_score: NewBandScore,
+    ) -> Result<BandScore> {
+        if let Some(space_id) = band_score.space_id {
+            space_quick_authorize(ctx, space_id, SpaceActionPermission::ManageSpaceContent).await?;
+        }
+        let user_id = get_user_id_from_ctx(ctx).await?;
+        band_score.user_id = Some(user_id);
+
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let band_score = BandScore::insert(&mut conn, band_score).format_err()?;
+        Ok(band_score)
+    }
+
+    async fn assignment_update_band_score(
+        &self,
+        ctx: &Context<'_>,
+        band_score_id: i32,
+        #[graphql(validator(custom = "ValidBandScore"))] band_score: NewBandScore,
+    ) -> Result<BandScore> {
+        band_score_quick_authorize(
+            ctx,
+            band_score_id,
+            BandScoreActionPermission::ManageBandScore,
+        )
+        .await?;
+        if let Some(space_id) = band_score.space_id {
+            space_quick_authorize(ctx, space_id, SpaceActionPermission::ManageSpaceContent).await?;
+        }
+
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let band_score = BandScore::update(&mut conn, band_score_id, band_score).format_err()?;
+        Ok(band_score)
+    }
+
+    async fn assignment_remove_band_score(
+        &self,
+        ctx: &Context<'_>,
+        band_score_id: i32,
+    ) -> Result<bool> {
+        band_score_quick_authorize(
+            ctx,
+            band_score_id,
+            BandScoreActionPermission::ManageBandScore,
+        )
+        .await?;
+
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        BandScore::remove(&mut conn, band_score_id).format_err()?;
+        Ok(true)
+    }
+
+    async fn assignment_migrate_rubric_submissions(
+        &self,
+        ctx: &Context<'_>,
+        assignment_id: i32,
+    ) -> Result<i32> {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let assignment = Assignment::find_by_id(&mut conn, assignment_id).format_err()?;
+        document_quick_authorize(
+            ctx,
+            assignment.document_id,
+            DocumentActionPermission::ManageDocument,
+        )
+        .await?;
+
+        let count = migrate_rubric_submissions(&mut conn, assignment_id).format_err()?;
+        Ok(count as i32)
+    }
+
+    async fn assignment_release_grades(
+        &self,
+        ctx: &Context<'_>,
+        assignment_id: i32,
+        at: Option<i64>,
+    ) -> Result<bool> {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let assignment = Assignment::find_by_id(&mut conn, assignment_id).format_err()?;
+        document_quick_authorize(
+            ctx,
+            assignment.document_id,
+            DocumentActionPermission::ManageDocument,
+        )
+        .await?;
+
+        let release_at = at.unwrap_or_default().max(get_now_as_secs());
+        Assignment::update_release_grades_at(&mut conn, assignment_id, Some(release_at))
+            .format_err()?;
+        let message = ReleaseGrades {
+            assignment_id,
+            release_at,
+        };
+        let job = JobBuilder::default()
+            .message(message)
+            .job_type(JobType::ScheduledAt(get_date_from_ts(release_at)))
+            .build()?;
+        AJ::add_job(job);
+
+        Ok(true)
+    }
+}
+
+// Load submissions and check permission once per assignment of them
+async fn authorize_submissions_to_grade(
+    ctx: &Context<'_>,
+    submission_ids: &[i32],
+) -> Result<(Vec<Submission>, HashMap<i32, Assignment>)> {
+    let mut submission_ids = submission_ids.to_vec();
+    submission_ids.sort();
+    submission_ids.dedup();
+
+    let (submissions, assignments) = {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let submissions =
+            Submission::find_all_by_ids(&mut conn, submission_ids.clone()).format_err()?;
+        let assignment_ids = submissions
+            .iter()
+            .map(|submission| submission.assignment_id)
+            .collect();
+        let assignments: HashMap<i32, Assignment> =
+            Assignment::find_all_by_ids(&mut conn, assignment_ids)
+                .format_err()?
+                .into_iter()
+                .map(|assignment| (assignment.id, assignment))
+                .collect();
+        (submissions, assignments)
+    };
+    if submissions.len() != submission_ids.len() {
+        return Err(IkigaiError::new_bad_request("Submission not found")).format_err();
+    }
+
+    for assignment in assignments.values() {
+        document_quick_authorize(
+            ctx,
+            assignment.document_id,
+            DocumentActionPermission::ManageDocument,
+        )
+        .await?;
+    }
+
+    Ok((submissions, assignments))
+}
+
+async fn authorize_pending_regrade_request(
+    ctx: &Context<'_>,
+    regrade_request_id: i32,
+) -> Result<RegradeRequest> {
+    let (request, assignment) = {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let request = RegradeRequest::find_by_id(&mut conn, regrade_request_id).format_err()?;
+        let submission = Submission::find_by_id(&mut conn, request.submission_id).format_err()?;
+        let assignment =
+            Assignment::find_by_id(&mut conn, submission.assignment_id).format_err()?;
+        (request, assignment)
+    };
+    document_quick_authorize(
+        ctx,
+        assignment.document_id,
+        DocumentActionPermission::ManageDocument,
+    )
+    .await?;
+
+    if request.status != RegradeRequestStatus::Pending {
+        return Err(IkigaiError::new_bad_request(
+            "Regrade request is already responded",
+        ))
+        .format_err();
+    }
+
+    Ok(request)
+}
+
+async fn authorize_feedback_annotation(ctx: &Context<'_>, annotation_id: Uuid) -> Result<()> {
+    let assignment = {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let annotation = FeedbackAnnotation::find_by_id(&mut conn, annotation_id).format_err()?;
+        let submission =
+            Submission::find_by_id(&mut conn, annotation.submission_id).format_err()?;
+        Assignment::find_by_id(&mut conn, submission.assignment_id).format_err()?
+    };
+    document_quick_authorize(
+        ctx,
+        assignment.document_id,
+        DocumentActionPermission::ManageDocument,
+    )
+    .await
+}
+
+async fn authorize_file_comment(ctx: &Context<'_>, file_comment_id: Uuid) -> Result<()> {
+    let assignment = {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let file_comment = FileComment::find_by_id(&mut conn, file_comment_id).format_err()?;
+        let submission =
+            Submission::find_by_id(&mut conn, file_comment.submission_id).format_err()?;
+        Assignment::find_by_id(&mut conn, submission.assignment_id).format_err()?
+    };
+    document_quick_authorize(
+        ctx,
+        assignment.document_id,
+        DocumentActionPermission::ManageDocument,
+    )
+    .await
 }
@@ -11,8 +11,15 @@ pub struct AssignmentQuery;
 #[Object]
 impl AssignmentQuery {
     async fn assignment_get_band_scores(&self, ctx: &Context<'_>) -> Result<Vec<BandScore>> {
+        let user_id = get_user_id_from_ctx(ctx).await?;
+        let space_id = get_user_auth_from_ctx(ctx)
+            .await
+            .ok()
+            .filter(|user_auth| user_auth.role == Role::Teacher)
+            .map(|user_auth| user_auth.space_id);
         let mut conn = get_conn_from_ctx(ctx).await?;
-        let band_scores = BandScore::find_all(&mut conn).format_err()?;
+        let band_scores =
+            BandScore::find_all_available(&mut conn, user_id, space_id).format_err()?;
         Ok(band_scores)
     }
 
@@ -52,4 +59,140 @@ impl AssignmentQuery {
 
         Ok(submissions)
     }
+
+    async fn assignment_get_accommodations(
+        &self,
+        ctx: &Context<'_>,
+        assignment_id: i32,
+    ) -> Result<Vec<AssignmentAccommodation>> {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let assignment = Assignment::find_by_id(&mut conn, assignment_id).format_err()?;
+        document_quick_authorize(
+            ctx,
+            assignment.document_id,
+            DocumentActionPermission::ManageDocument,
+        )
+        .await?;
+
+        let accommodations =
+            AssignmentAccommodation::find_all_by_assignment(&mut conn, assignment_id)
+                .format_err()?;
+        Ok(accommodations)
+    }
+
+    async fn assignment_get_grade_histories(
+        &self,
+        ctx: &Context<'_>,
+        submission_id: i32,
+    ) -> Result<Vec<SubmissionGradeHistory>> {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let submission = Submission::find_by_id(&mut conn, submission_id).format_err()?;
+        let assignment =
+            Assignment::find_by_id(&mut conn, submission.assignment_id).format_err()?;
+        document_quick_authorize(
+            ctx,
+            assignment.document_id,
+            DocumentActionPermission::ManageDocument,
+        )
+        .await?;
+
+        SubmissionGradeHistory::find_all_by_submission(&mut conn, submission_id).format_err()
+    }
+
+    async fn assignment_get_marking_summaries(
+        &self,
+        ctx: &Context<'_>,
+        assignment_id: i32,
+    ) -> Result<Vec<MarkingSummary>> {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let assignment = Assignment::find_by_id(&mut conn, assignment_id).format_err()?;
+        document_quick_authorize(
+            ctx,
+            assignment.document_id,
+            DocumentActionPermission::ManageDocument,
+        )
+        .await?;
+
+        build_marking_summaries(&mut conn, &assignment).format_err()
+    }
+
+    async fn assignment_get_my_peer_reviews(
+        &self,
+        ctx: &Context<'_>,
+        assignment_id: i32,
+    ) -> Result<Vec<PeerReview>> {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let assignment = Assignment::find_by_id(&mut conn, assignment_id).format_err()?;
+        document_quick_authorize(
+            ctx,
+            assignment.document_id,
+            DocumentActionPermission::ViewDocument,
+        )
+        .await?;
+
+        let user_id = get_user_id_from_ctx(ctx).await?;
+        PeerReview::find_all_by_reviewer_and_assignment(&mut conn, user_id, assignment_id)
+            .format_err()
+    }
+
+    async fn assignment_get_regrade_requests(
+        &self,
+        ctx: &Context<'_>,
+        submission_id: i32,
+    ) -> Result<Vec<RegradeRequest>> {
+        let user_id = get_user_id_from_ctx(ctx).await?;
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let submission = Submission::find_by_id(&mut conn, submission_id).format_err()?;
+        if submission.user_id != user_id {
+            let assignment =
+                Assignment::find_by_id(&mut conn, submission.assignment_id).format_err()?;
+            document_quick_authorize(
+                ctx,
+                assignment.document_id,
+                DocumentActionPermission::ManageDocument,
+            )
+            .await?;
+        }
+
+        RegradeRequest::find_all_by_submission(&mut conn, submission_id).format_err()
+    }
+
+    async fn assignment_get_pending_regrade_requests(
+        &self,
+        ctx: &Context<'_>,
+        assignment_id: i32,
+    ) -> Result<Vec<RegradeRequest>> {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let assignment = Assignment::find_by_id(&mut conn, assignment_id).format_err()?;
+        document_quick_authorize(
+            ctx,
+            assignment.document_id,
+            DocumentActionPermission::ManageDocument,
+        )
+        .await?;
+
+        let submission_ids: Vec<i32> = Submission::find_all_by_assignment(&mut conn, assignment_id)
+            .format_err()?
+            .into_iter()
+            .map(|submission| submission.id)
+            .collect();
+        RegradeRequest::find_all_pending_by_submissions(&mut conn, &submission_ids).format_err()
+    }
+
+    async fn assignment_get_feedback_annotations(
+        &self,
+        ctx: &Context<'_>,
+        submission_id: i32,
+    ) -> Result<Vec<FeedbackAnnotation>> {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let submission = Submission::find_by_id(&mut conn, submission_id).format_err()?;
+        document_quick_authorize(
+            ctx,
+            submission.document_id,
+            DocumentActionPermission::ViewAnswer,
+        )
+        .await?;
+
+        FeedbackAnnotation::find_all_by_submission(&mut conn, submission_id).format_err()
+    }
 }
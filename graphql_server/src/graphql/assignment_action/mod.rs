@@ -13,11 +13,11 @@ use crate::authorization::DocumentActionPermission;
 use crate::db::*;
 use crate::error::IkigaiErrorExt;
 use crate::graphql::data_loader::{
-    AssignmentById, DocumentById, IkigaiDataLoader, SubmissionByAssignmentId,
+    AssignmentById, DocumentById, IkigaiDataLoader, SubmissionByAssignmentId, SubmissionById,
 };
 use crate::helper::{
-    document_quick_authorize, get_conn_from_ctx, get_public_user_from_loader,
-    get_user_auth_from_ctx, get_user_id_from_ctx,
+    document_quick_authorize, generate_download_url, get_conn_from_ctx,
+    get_public_user_from_loader, get_user_auth_from_ctx, get_user_id_from_ctx,
 };
 
 #[ComplexObject]
@@ -63,6 +63,33 @@ impl Assignment {
         }
     }
 
+    async fn effective_grade(
+        &self,
+        ctx: &Context<'_>,
+        user_id: Option<i32>,
+    ) -> Result<Option<f64>> {
+        let current_user_id = get_user_id_from_ctx(ctx).await?;
+        let is_manager = document_quick_authorize(
+            ctx,
+            self.document_id,
+            DocumentActionPermission::ManageDocument,
+        )
+        .await
+        .is_ok();
+        let user_id = user_id.unwrap_or(current_user_id);
+        if user_id != current_user_id && !is_manager {
+            return Ok(None);
+        }
+
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let submissions = Submission::find_all_by_assignment_and_user(&mut conn, user_id, self.id)
+            .format_err()?
+            .into_iter()
+            .filter(|submission| is_manager || submission.allow_for_student_view_answer)
+            .collect::<Vec<Submission>>();
+        Ok(self.calculate_effective_grade(&submissions))
+    }
+
     async fn rubric(&self, ctx: &Context<'_>) -> Result<Option<Rubric>> {
         if let Some(rubric_id) = self.grade_by_rubric_id {
             let mut conn = get_conn_from_ctx(ctx).await?;
@@ -76,21 +103,30 @@ impl Assignment {
 
 #[ComplexObject]
 impl Submission {
-    async fn status(&self) -> SubmissionStatus {
-        self.submission_status()
+    async fn status(&self, ctx: &Context<'_>) -> Result<SubmissionStatus> {
+        let user_auth = get_user_auth_from_ctx(ctx).await?;
+        Ok(self.submission_status(user_auth.role != Role::Student))
     }
 
-    async fn user(&self, ctx: &Context<'_>) -> Result<PublicUser> {
-        get_public_user_from_loader(ctx, self.user_id).await
+    async fn user_id(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+        if is_identity_hidden(ctx, self).await? {
+            return Ok(None);
+        }
+
+        Ok(Some(self.user_id))
+    }
+
+    async fn user(&self, ctx: &Context<'_>) -> Result<Option<PublicUser>> {
+        if is_identity_hidden(ctx, self).await? {
+            return Ok(None);
+        }
+
+        let user = get_public_user_from_loader(ctx, self.user_id).await?;
+        Ok(Some(user))
     }
 
     async fn assignment(&self, ctx: &Context<'_>) -> Result<Assignment> {
-        let loader = ctx.data_unchecked::<DataLoader<IkigaiDataLoader>>();
-        let assignment = loader
-            .load_one(AssignmentById(self.assignment_id))
-            .await?
-            .ok_or(format!("Not found assignment {}", self.assignment_id))?;
-        Ok(assignment)
+        get_assignment(ctx, self.assignment_id).await
     }
 
     async fn document(&self, ctx: &Context<'_>) -> Result<Document> {
@@ -119,6 +155,53 @@ impl Submission {
         }
     }
 
+    async fn feedback(&self, ctx: &Context<'_>) -> Option<String> {
+        let user_auth = get_user_auth_from_ctx(ctx).await.ok()?;
+        if user_auth.role != Role::Student {
+            return self.feedback.clone();
+        }
+
+        if self.grade_held {
+            None
+        } else {
+            self.feedback.clone()
+        }
+    }
+
+    async fn peer_reviews(&self, ctx: &Context<'_>) -> Result<Vec<PeerReview>> {
+        let user_auth = get_user_auth_from_ctx(ctx).await?;
+        if user_auth.role == Role::Student {
+            return Ok(vec![]);
+        }
+
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        PeerReview::find_all_by_submission(&mut conn, self.id).format_err()
+    }
+
+    async fn peer_review_score(&self, ctx: &Context<'_>) -> Result<Option<f64>> {
+        let user_auth = get_user_auth_from_ctx(ctx).await?;
+        if user_auth.role == Role::Student {
+            return Ok(None);
+        }
+
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let reviews = PeerReview::find_all_by_submission(&mut conn, self.id).format_err()?;
+        Ok(PeerReview::average_score(&reviews))
+    }
+
+    // Feedback files require ViewAnswer on the submission document
+    async fn feedback_files(&self, ctx: &Context<'_>) -> Result<Vec<SubmissionFeedbackFile>> {
+        if document_quick_authorize(ctx, self.document_id, DocumentActionPermission::ViewAnswer)
+            .await
+            .is_err()
+        {
+            return Ok(vec![]);
+        }
+
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        SubmissionFeedbackFile::find_all_by_submission(&mut conn, self.id).format_err()
+    }
+
     async fn final_grade(&self, ctx: &Context<'_>) -> Option<f64> {
         let user_auth = get_user_auth_from_ctx(ctx).await.ok()?;
         if user_auth.role != Role::Student {
@@ -133,6 +216,107 @@ impl Submission {
     }
 }
 
+#[ComplexObject]
+impl AssignmentAccommodation {
+    async fn user(&self, ctx: &Context<'_>) -> Result<PublicUser> {
+        get_public_user_from_loader(ctx, self.user_id).await
+    }
+}
+
+#[ComplexObject]
+impl SubmissionMarking {
+    async fn marker(&self, ctx: &Context<'_>) -> Result<PublicUser> {
+        get_public_user_from_loader(ctx, self.marker_id).await
+    }
+}
+
+#[ComplexObject]
+impl PeerReview {
+    // Reviewer opens this document to review the submission
+    async fn document_id(&self, ctx: &Context<'_>) -> Result<Uuid> {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let submission = Submission::find_by_id(&mut conn, self.submission_id).format_err()?;
+        Ok(submission.document_id)
+    }
+
+    async fn reviewer(&self, ctx: &Context<'_>) -> Result<Option<PublicUser>> {
+        let user_auth = get_user_auth_from_ctx(ctx).await?;
+        if user_auth.role == Role::Student && user_auth.id != self.reviewer_id {
+            return Ok(None);
+        }
+
+        let user = get_public_user_from_loader(ctx, self.reviewer_id).await?;
+        Ok(Some(user))
+    }
+}
+
+#[ComplexObject]
+impl RegradeRequest {
+    async fn requester(&self, ctx: &Context<'_>) -> Result<Option<PublicUser>> {
+        let submission = get_submission(ctx, self.submission_id).await?;
+        if is_identity_hidden(ctx, &submission).await? {
+            return Ok(None);
+        }
+
+        let user = get_public_user_from_loader(ctx, self.requester_id).await?;
+        Ok(Some(user))
+    }
+
+    async fn responder(&self, ctx: &Context<'_>) -> Result<Option<PublicUser>> {
+        if let Some(responder_id) = self.responder_id {
+            let user = get_public_user_from_loader(ctx, responder_id).await?;
+            return Ok(Some(user));
+        }
+
+        Ok(None)
+    }
+}
+
+#[ComplexObject]
+impl FeedbackAnnotation {
+    async fn creator(&self, ctx: &Context<'_>) -> Result<PublicUser> {
+        get_public_user_from_loader(ctx, self.creator_id).await
+    }
+}
+
+#[ComplexObject]
+impl FileComment {
+    async fn creator(&self, ctx: &Context<'_>) -> Result<PublicUser> {
+        get_public_user_from_loader(ctx, self.creator_id).await
+    }
+}
+
+#[ComplexObject]
+impl SubmissionFeedbackFile {
+    async fn file(&self, ctx: &Context<'_>) -> Result<File> {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        File::find_by_id(&mut conn, self.file_id).format_err()
+    }
+
+    async fn download_url(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        let file = {
+            let mut conn = get_conn_from_ctx(ctx).await?;
+            File::find_by_id(&mut conn, self.file_id).format_err()?
+        };
+        generate_download_url(&file, ctx).await
+    }
+
+    async fn creator(&self, ctx: &Context<'_>) -> Result<PublicUser> {
+        get_public_user_from_loader(ctx, self.creator_id).await
+    }
+}
+
+#[ComplexObject]
+impl SubmissionGradeHistory {
+    async fn actor(&self, ctx: &Context<'_>) -> Result<Option<PublicUser>> {
+        if let Some(actor_id) = self.actor_id {
+            Ok(Some(get_public_user_from_loader(ctx, actor_id).await?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 #[ComplexObject]
 impl RubricTableData {
     async fn total_user_score(&self) -> f64 {
@@ -140,6 +324,30 @@ impl RubricTableData {
     }
 }
 
+async fn get_assignment(ctx: &Context<'_>, assignment_id: i32) -> Result<Assignment> {
+    let loader = ctx.data_unchecked::<DataLoader<IkigaiDataLoader>>();
+    let assignment = loader
+        .load_one(AssignmentById(assignment_id))
+        .await?
+        .ok_or(format!("Not found assignment {}", assignment_id))?;
+    Ok(assignment)
+}
+
+async fn is_identity_hidden(ctx: &Context<'_>, submission: &Submission) -> Result<bool> {
+    let user_id = get_user_id_from_ctx(ctx).await?;
+    let assignment = get_assignment(ctx, submission.assignment_id).await?;
+    Ok(submission.is_identity_hidden(&assignment, user_id))
+}
+
+async fn get_submission(ctx: &Context<'_>, submission_id: i32) -> Result<Submission> {
+    let loader = ctx.data_unchecked::<DataLoader<IkigaiDataLoader>>();
+    let submission = loader
+        .load_one(SubmissionById(submission_id))
+        .await?
+        .ok_or(format!("Cannot found submission {submission_id}"))?;
+    Ok(submission)
+}
+
 async fn get_document(ctx: &Context<'_>, document_id: Uuid) -> Result<Document> {
     let loader = ctx.data_unchecked::<DataLoader<IkigaiDataLoader>>();
     let document = loader
@@ -290,6 +290,29 @@ impl Loader<DocumentById> for IkigaiDataLoader {
     }
 }
 
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub struct DocumentIdByPageContentId(pub Uuid);
+
+impl Loader<DocumentIdByPageContentId> for IkigaiDataLoader {
+    type Value = Uuid;
+    type Error = IkigaiError;
+
+    async fn load(
+        &self,
+        keys: &[DocumentIdByPageContentId],
+    ) -> Result<HashMap<DocumentIdByPageContentId, Self::Value>, Self::Error> {
+        let mut conn = get_conn_from_actor().await?;
+        let page_content_ids = keys.iter().map(|c| c.0).unique().collect();
+
+        Ok(PageContent::find_document_ids(&mut conn, page_content_ids)?
+            .into_iter()
+            .map(|(page_content_id, document_id)| {
+                (DocumentIdByPageContentId(page_content_id), document_id)
+            })
+            .collect())
+    }
+}
+
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
 pub struct FindDocumentType(pub Uuid);
 
@@ -414,4 +414,135 @@ impl DocumentMutation {
         let writing_block = WritingBlock::upsert(&mut conn, writing_block).format_err()?;
         Ok(writing_block)
     }
+
+    async fn document_upsert_quiz_block(
+        &self,
+        ctx: &Context<'_>,
+        page_content_id: Uuid,
+        mut quiz_block: QuizBlock,
+        answer_key: Option<QuizAnswer>,
+    ) -> Result<QuizBlock> {
+        let user_id = get_user_id_from_ctx(ctx).await?;
+        let page = {
+            let mut conn = get_conn_from_ctx(ctx).await?;
+            let page_content = PageContent::find(&mut conn, page_content_id).format_err()?;
+            Page::find(&mut conn, page_content.page_id).format_err()?
+        };
+        // Score and answer key are part of quiz block, students must not change them
+        document_quick_authorize(
+            ctx,
+            page.document_id,
+            DocumentActionPermission::ManageDocument,
+        )
+        .await?;
+
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        if let Ok(existing_quiz_block) = QuizBlock::find(&mut conn, quiz_block.id) {
+            if existing_quiz_block.page_content_id != page_content_id {
+                return Err(IkigaiError::new_bad_request(
+                    "Cannot update quiz block of other page",
+                ))
+                .format_err();
+            }
+        }
+
+        quiz_block.page_content_id = page_content_id;
+        quiz_block.creator_id = user_id;
+        let quiz_block = conn
+            .transaction::<_, IkigaiError, _>(|conn| {
+                let quiz_block = QuizBlock::upsert(conn, quiz_block)?;
+                if let Some(answer_key) = answer_key {
+                    QuizAnswerKey::upsert(conn, QuizAnswerKey::new(quiz_block.id, answer_key))?;
+                }
+                Ok(quiz_block)
+            })
+            .format_err()?;
+
+        Ok(quiz_block)
+    }
+
+    async fn document_clone_quiz_block(
+        &self,
+        ctx: &Context<'_>,
+        quiz_block_id: Uuid,
+        new_quiz_block_id: Uuid,
+        new_page_content_id: Uuid,
+    ) -> Result<QuizBlock> {
+        let new_page = {
+            let mut conn = get_conn_from_ctx(ctx).await?;
+            let page_content = PageContent::find(&mut conn, new_page_content_id).format_err()?;
+            Page::find(&mut conn, page_content.page_id).format_err()?
+        };
+        document_quick_authorize(
+            ctx,
+            new_page.document_id,
+            DocumentActionPermission::ManageDocument,
+        )
+        .await?;
+
+        let (mut quiz_block, old_page) = {
+            let mut conn = get_conn_from_ctx(ctx).await?;
+            let quiz_block = QuizBlock::find(&mut conn, quiz_block_id).format_err()?;
+            let page_content =
+                PageContent::find(&mut conn, quiz_block.page_content_id).format_err()?;
+            let old_page = Page::find(&mut conn, page_content.page_id).format_err()?;
+            (quiz_block, old_page)
+        };
+        document_quick_authorize(
+            ctx,
+            old_page.document_id,
+            DocumentActionPermission::ManageDocument,
+        )
+        .await?;
+
+        quiz_block.creator_id = get_user_id_from_ctx(ctx).await?;
+        quiz_block.page_content_id = new_page_content_id;
+        quiz_block.id = new_quiz_block_id;
+        quiz_block.answer = None;
+
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        if QuizBlock::find(&mut conn, new_quiz_block_id).is_ok() {
+            return Err(IkigaiError::new_bad_request(
+                "Quiz block already existed in other pages",
+            ))
+            .format_err();
+        }
+        let quiz_block = conn
+            .transaction::<_, IkigaiError, _>(|conn| {
+                let new_quiz_block = QuizBlock::upsert(conn, quiz_block)?;
+                if let Some(answer_key) = QuizAnswerKey::find_opt(conn, quiz_block_id)? {
+                    let new_answer_key = QuizAnswerKey::new(new_quiz_block.id, answer_key.answer);
+                    QuizAnswerKey::upsert(conn, new_answer_key)?;
+                }
+                Ok(new_quiz_block)
+            })
+            .format_err()?;
+
+        Ok(quiz_block)
+    }
+
+    async fn document_answer_quiz_block(
+        &self,
+        ctx: &Context<'_>,
+        quiz_block_id: Uuid,
+        answer: QuizAnswer,
+    ) -> Result<QuizBlock> {
+        let page = {
+            let mut conn = get_conn_from_ctx(ctx).await?;
+            let quiz_block = QuizBlock::find(&mut conn, quiz_block_id).format_err()?;
+            let page_content =
+                PageContent::find(&mut conn, quiz_block.page_content_id).format_err()?;
+            Page::find(&mut conn, page_content.page_id).format_err()?
+        };
+        document_quick_authorize(
+            ctx,
+            page.document_id,
+            DocumentActionPermission::InteractiveWithTool,
+        )
+        .await?;
+
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let quiz_block = QuizBlock::update_answer(&mut conn, quiz_block_id, answer).format_err()?;
+        Ok(quiz_block)
+    }
 }
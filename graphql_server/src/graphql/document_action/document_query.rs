@@ -69,4 +69,28 @@ impl DocumentQuery {
 
         Ok(writing_block)
     }
+
+    async fn document_get_quiz_block(
+        &self,
+        ctx: &Context<'_>,
+        quiz_block_id: Uuid,
+    ) -> Result<QuizBlock> {
+        let (quiz_block, page) = {
+            let mut conn = get_conn_from_ctx(ctx).await?;
+            let quiz_block = QuizBlock::find(&mut conn, quiz_block_id).format_err()?;
+            let page_content =
+                PageContent::find(&mut conn, quiz_block.page_content_id).format_err()?;
+            let page = Page::find(&mut conn, page_content.page_id).format_err()?;
+            (quiz_block, page)
+        };
+
+        document_quick_authorize(
+            ctx,
+            page.document_id,
+            DocumentActionPermission::ViewDocument,
+        )
+        .await?;
+
+        Ok(quiz_block)
+    }
 }
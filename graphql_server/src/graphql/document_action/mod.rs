@@ -7,17 +7,34 @@ pub use document_query::*;
 use crate::authorization::DocumentActionPermission;
 use async_graphql::dataloader::DataLoader;
 use async_graphql::*;
+use uuid::Uuid;
 
 use crate::db::*;
 use crate::error::IkigaiErrorExt;
 use crate::graphql::data_loader::*;
 use crate::helper::{
     document_quick_authorize, generate_download_url, get_conn_from_ctx,
-    get_public_user_from_loader, get_user_id_from_ctx,
+    get_public_user_from_loader, get_user_id_from_ctx, is_document_identity_hidden,
 };
 
 #[ComplexObject]
 impl Document {
+    async fn title(&self, ctx: &Context<'_>) -> Result<String> {
+        if is_document_identity_hidden(ctx, self.id).await? {
+            return anonymous_submission_title(ctx, self.id).await;
+        }
+
+        Ok(self.title.clone())
+    }
+
+    async fn creator_id(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+        if is_document_identity_hidden(ctx, self.id).await? {
+            return Ok(None);
+        }
+
+        Ok(Some(self.creator_id))
+    }
+
     async fn assignment(&self, ctx: &Context<'_>) -> Result<Option<Assignment>> {
         if get_user_id_from_ctx(ctx).await.is_err() {
             return Ok(None);
@@ -73,6 +90,10 @@ impl Document {
     }
 
     async fn updated_by(&self, ctx: &Context<'_>) -> Option<PublicUser> {
+        if is_document_identity_hidden(ctx, self.id).await.ok()? {
+            return None;
+        }
+
         if let Some(updated_by) = self.updated_by {
             get_public_user_from_loader(ctx, updated_by).await.ok()
         } else {
@@ -100,6 +121,14 @@ impl Document {
 
 #[ComplexObject]
 impl Page {
+    async fn created_by_id(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+        if is_document_identity_hidden(ctx, self.document_id).await? {
+            return Ok(None);
+        }
+
+        Ok(Some(self.created_by_id))
+    }
+
     async fn page_contents(&self, ctx: &Context<'_>) -> Result<Vec<PageContent>> {
         let loader = ctx.data_unchecked::<DataLoader<IkigaiDataLoader>>();
         let page_contents = loader
@@ -116,3 +145,86 @@ impl DocumentAssignedUsers {
         get_public_user_from_loader(ctx, self.assigned_user_id).await
     }
 }
+
+#[ComplexObject]
+impl WritingBlock {
+    async fn creator_id(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+        if is_page_content_identity_hidden(ctx, self.page_content_id).await? {
+            return Ok(None);
+        }
+
+        Ok(Some(self.creator_id))
+    }
+}
+
+#[ComplexObject]
+impl QuizBlock {
+    async fn creator_id(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+        if is_page_content_identity_hidden(ctx, self.page_content_id).await? {
+            return Ok(None);
+        }
+
+        Ok(Some(self.creator_id))
+    }
+
+    async fn answer_key(&self, ctx: &Context<'_>) -> Result<Option<QuizAnswer>> {
+        let page = {
+            let mut conn = get_conn_from_ctx(ctx).await?;
+            let page_content = PageContent::find(&mut conn, self.page_content_id).format_err()?;
+            Page::find(&mut conn, page_content.page_id).format_err()?
+        };
+        if document_quick_authorize(ctx, page.document_id, DocumentActionPermission::ViewAnswer)
+            .await
+            .is_err()
+        {
+            return Ok(None);
+        }
+
+        // Quiz blocks of submission do not keep answer key, read it from the original
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let mut answer_key = QuizAnswerKey::find_opt(&mut conn, self.id).format_err()?;
+        if let (None, Some(original_quiz_block_id)) = (&answer_key, self.original_quiz_block_id) {
+            answer_key = QuizAnswerKey::find_opt(&mut conn, original_quiz_block_id).format_err()?;
+        }
+        Ok(answer_key.map(|answer_key| answer_key.answer))
+    }
+}
+
+async fn is_page_content_identity_hidden(ctx: &Context<'_>, page_content_id: Uuid) -> Result<bool> {
+    let loader = ctx.data_unchecked::<DataLoader<IkigaiDataLoader>>();
+    let document_id = loader
+        .load_one(DocumentIdByPageContentId(page_content_id))
+        .await?
+        .ok_or(format!("Cannot found the page content {page_content_id}"))?;
+    is_document_identity_hidden(ctx, document_id).await
+}
+
+// Stored title of submission has student name, build it with the pseudonym instead
+async fn anonymous_submission_title(ctx: &Context<'_>, document_id: Uuid) -> Result<String> {
+    let loader = ctx.data_unchecked::<DataLoader<IkigaiDataLoader>>();
+    let submission = loader
+        .load_one(SubmissionByDocumentId(document_id))
+        .await?
+        .ok_or(format!(
+            "Cannot found the submission of document {document_id}"
+        ))?;
+    let assignment = loader
+        .load_one(AssignmentById(submission.assignment_id))
+        .await?
+        .ok_or(format!(
+            "Cannot found the assignment {}",
+            submission.assignment_id
+        ))?;
+    let assignment_document = loader
+        .load_one(DocumentById(assignment.document_id))
+        .await?
+        .ok_or(format!(
+            "Cannot found the document {}",
+            assignment.document_id
+        ))?;
+    Ok(format!(
+        "[{}] {}",
+        submission.pseudonym(),
+        assignment_document.title
+    ))
+}
@@ -4,7 +4,9 @@ use async_graphql::*;
 use uuid::Uuid;
 
 use crate::db::file::File;
+use crate::db::{FileComment, Submission};
 use crate::error::IkigaiErrorExt;
+use crate::graphql::file_action::FileWaveform;
 use crate::helper::{document_quick_authorize, get_conn_from_ctx};
 
 #[derive(Default)]
@@ -23,7 +25,7 @@ impl FileQuery {
         ctx: &Context<'_>,
         file_id: Uuid,
         document_id: Uuid,
-    ) -> Result<Option<String>> {
+    ) -> Result<FileWaveform> {
         document_quick_authorize(ctx, document_id, DocumentActionPermission::ViewDocument).await?;
 
         let mut conn = get_conn_from_ctx(ctx).await?;
@@ -35,6 +37,35 @@ impl FileQuery {
             add_generate_waveform_job(file.uuid);
         }
 
-        Ok(file.waveform_audio_json_str)
+        let comments = get_visible_file_comments(ctx, document_id, file_id).await?;
+        Ok(FileWaveform {
+            waveform: file.waveform_audio_json_str,
+            comments,
+        })
     }
 }
+
+// Comments on a submission file require ViewAnswer on the submission document
+async fn get_visible_file_comments(
+    ctx: &Context<'_>,
+    document_id: Uuid,
+    file_id: Uuid,
+) -> Result<Vec<FileComment>> {
+    let submission = {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        Submission::find_by_document(&mut conn, document_id).format_err()?
+    };
+    if let Some(submission) = submission {
+        let can_view_answer =
+            document_quick_authorize(ctx, document_id, DocumentActionPermission::ViewAnswer)
+                .await
+                .is_ok();
+        if can_view_answer {
+            let mut conn = get_conn_from_ctx(ctx).await?;
+            return FileComment::find_all_by_submission_and_file(&mut conn, submission.id, file_id)
+                .format_err();
+        }
+    }
+
+    Ok(vec![])
+}
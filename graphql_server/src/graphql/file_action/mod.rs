@@ -5,11 +5,11 @@ pub use file_mutation::*;
 pub use file_query::*;
 
 use async_graphql::dataloader::DataLoader;
-use async_graphql::{ComplexObject, Context, Enum, Result};
+use async_graphql::{ComplexObject, Context, Enum, Result, SimpleObject};
 use uuid::Uuid;
 
 use crate::authorization::DocumentActionPermission;
-use crate::db::{File, Page, PageContent, PublicUser};
+use crate::db::{File, FileComment, Page, PageContent, PublicUser};
 use crate::error::{IkigaiError, IkigaiErrorExt};
 use crate::graphql::data_loader::{FindPublicUserById, IkigaiDataLoader};
 use crate::helper::{document_quick_authorize, generate_download_url, get_conn_from_ctx};
@@ -23,6 +23,13 @@ pub enum FileType {
     Other,
 }
 
+#[derive(SimpleObject, Debug, Clone)]
+pub struct FileWaveform {
+    pub waveform: Option<String>,
+    // Feedback comments on the audio, only when file is in a submission viewer can see feedback of
+    pub comments: Vec<FileComment>,
+}
+
 #[ComplexObject]
 impl File {
     async fn download_url_by_page_content_id(
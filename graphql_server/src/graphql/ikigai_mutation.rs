@@ -3,6 +3,7 @@ use async_graphql::*;
 use crate::graphql::assignment_action::AssignmentMutation;
 use crate::graphql::document_action::DocumentMutation;
 use crate::graphql::file_action::FileMutation;
+use crate::graphql::mock_test_action::MockTestMutation;
 use crate::graphql::space_action::SpaceMutation;
 use crate::graphql::user_action::UserMutation;
 
@@ -13,4 +14,5 @@ pub struct IkigaiMutation(
     FileMutation,
     SpaceMutation,
     DocumentMutation,
+    MockTestMutation,
 );
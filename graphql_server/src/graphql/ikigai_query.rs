@@ -3,6 +3,7 @@ use async_graphql::*;
 use crate::graphql::assignment_action::AssignmentQuery;
 use crate::graphql::document_action::DocumentQuery;
 use crate::graphql::file_action::FileQuery;
+use crate::graphql::mock_test_action::MockTestQuery;
 use crate::graphql::space_action::SpaceQuery;
 use crate::graphql::user_action::UserQuery;
 
@@ -13,4 +14,5 @@ pub struct IkigaiQuery(
     FileQuery,
     SpaceQuery,
     DocumentQuery,
+    MockTestQuery,
 );
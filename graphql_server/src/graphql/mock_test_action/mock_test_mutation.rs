@@ -0,0 +1,109 @@
+use async_graphql::*;
+use uuid::Uuid;
+
+use crate::authorization::SpaceActionPermission;
+use crate::db::*;
+use crate::error::{IkigaiError, IkigaiErrorExt};
+use crate::helper::{
+    get_conn_from_ctx, get_user_id_from_ctx, is_band_graded, space_quick_authorize,
+};
+
+#[derive(Default)]
+pub struct MockTestMutation;
+
+#[Object]
+impl MockTestMutation {
+    async fn mock_test_create(
+        &self,
+        ctx: &Context<'_>,
+        space_id: i32,
+        name: String,
+    ) -> Result<MockTest> {
+        space_quick_authorize(ctx, space_id, SpaceActionPermission::ManageSpaceContent).await?;
+        let user_id = get_user_id_from_ctx(ctx).await?;
+
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let new_mock_test = NewMockTest::new(space_id, user_id, name);
+        MockTest::insert(&mut conn, new_mock_test).format_err()
+    }
+
+    async fn mock_test_update(
+        &self,
+        ctx: &Context<'_>,
+        mock_test_id: i32,
+        name: String,
+    ) -> Result<MockTest> {
+        manage_mock_test_authorize(ctx, mock_test_id).await?;
+
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        MockTest::update_name(&mut conn, mock_test_id, name).format_err()
+    }
+
+    async fn mock_test_remove(&self, ctx: &Context<'_>, mock_test_id: i32) -> Result<bool> {
+        manage_mock_test_authorize(ctx, mock_test_id).await?;
+
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        MockTest::remove(&mut conn, mock_test_id).format_err()?;
+        Ok(true)
+    }
+
+    async fn mock_test_upsert_section(
+        &self,
+        ctx: &Context<'_>,
+        mock_test_id: i32,
+        skill: MockTestSkill,
+        document_id: Uuid,
+    ) -> Result<MockTestSection> {
+        let mock_test = manage_mock_test_authorize(ctx, mock_test_id).await?;
+
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let document = Document::find_by_id(&mut conn, document_id).format_err()?;
+        let assignment = Assignment::find_by_document(&mut conn, document_id).format_err()?;
+        let assignment = match assignment {
+            Some(assignment) if document.space_id == Some(mock_test.space_id) => assignment,
+            _ => {
+                return Err(IkigaiError::new_bad_request(
+                    "Section must be an assignment in the same space",
+                ))
+                .format_err()
+            }
+        };
+        if !is_band_graded(&mut conn, &assignment).format_err()? {
+            return Err(IkigaiError::new_bad_request(
+                "Section must be graded by a band score or an IELTS band rubric",
+            ))
+            .format_err();
+        }
+
+        let section = MockTestSection::new(mock_test_id, skill, document_id);
+        MockTestSection::upsert(&mut conn, section).format_err()
+    }
+
+    async fn mock_test_remove_section(
+        &self,
+        ctx: &Context<'_>,
+        mock_test_id: i32,
+        skill: MockTestSkill,
+    ) -> Result<bool> {
+        manage_mock_test_authorize(ctx, mock_test_id).await?;
+
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        MockTestSection::remove(&mut conn, mock_test_id, skill).format_err()?;
+        Ok(true)
+    }
+}
+
+async fn manage_mock_test_authorize(ctx: &Context<'_>, mock_test_id: i32) -> Result<MockTest> {
+    let mock_test = {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        MockTest::find_by_id(&mut conn, mock_test_id).format_err()?
+    };
+    space_quick_authorize(
+        ctx,
+        mock_test.space_id,
+        SpaceActionPermission::ManageSpaceContent,
+    )
+    .await?;
+
+    Ok(mock_test)
+}
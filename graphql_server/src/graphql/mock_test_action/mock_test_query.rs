@@ -0,0 +1,37 @@
+use async_graphql::*;
+
+use crate::authorization::SpaceActionPermission;
+use crate::db::*;
+use crate::error::IkigaiErrorExt;
+use crate::helper::{get_conn_from_ctx, space_quick_authorize};
+
+#[derive(Default)]
+pub struct MockTestQuery;
+
+#[Object]
+impl MockTestQuery {
+    async fn mock_test_get(&self, ctx: &Context<'_>, mock_test_id: i32) -> Result<MockTest> {
+        let mock_test = {
+            let mut conn = get_conn_from_ctx(ctx).await?;
+            MockTest::find_by_id(&mut conn, mock_test_id).format_err()?
+        };
+        space_quick_authorize(
+            ctx,
+            mock_test.space_id,
+            SpaceActionPermission::ViewSpaceContent,
+        )
+        .await?;
+
+        Ok(mock_test)
+    }
+
+    async fn mock_test_get_by_space(
+        &self,
+        ctx: &Context<'_>,
+        space_id: i32,
+    ) -> Result<Vec<MockTest>> {
+        space_quick_authorize(ctx, space_id, SpaceActionPermission::ViewSpaceContent).await?;
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        MockTest::find_all_by_space(&mut conn, space_id).format_err()
+    }
+}
@@ -0,0 +1,67 @@
+pub mod mock_test_mutation;
+pub mod mock_test_query;
+
+pub use mock_test_mutation::*;
+pub use mock_test_query::*;
+
+use async_graphql::dataloader::DataLoader;
+use async_graphql::{ComplexObject, Context, Result};
+
+use crate::authorization::SpaceActionPermission;
+use crate::db::*;
+use crate::error::IkigaiErrorExt;
+use crate::graphql::data_loader::{DocumentById, IkigaiDataLoader};
+use crate::helper::{
+    build_mock_test_progress, get_conn_from_ctx, get_public_user_from_loader, get_user_id_from_ctx,
+    space_quick_authorize, MockTestProgress,
+};
+
+#[ComplexObject]
+impl MockTest {
+    async fn creator(&self, ctx: &Context<'_>) -> Result<PublicUser> {
+        get_public_user_from_loader(ctx, self.creator_id).await
+    }
+
+    async fn sections(&self, ctx: &Context<'_>) -> Result<Vec<MockTestSection>> {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        MockTestSection::find_all_by_mock_test(&mut conn, self.id).format_err()
+    }
+
+    async fn progress(
+        &self,
+        ctx: &Context<'_>,
+        user_id: Option<i32>,
+    ) -> Result<Option<MockTestProgress>> {
+        let current_user_id = get_user_id_from_ctx(ctx).await?;
+        let is_manager = space_quick_authorize(
+            ctx,
+            self.space_id,
+            SpaceActionPermission::ManageSpaceContent,
+        )
+        .await
+        .is_ok();
+        let user_id = user_id.unwrap_or(current_user_id);
+        if user_id != current_user_id && !is_manager {
+            return Ok(None);
+        }
+
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let progress =
+            build_mock_test_progress(&mut conn, self.id, user_id, is_manager).format_err()?;
+        Ok(Some(progress))
+    }
+}
+
+#[ComplexObject]
+impl MockTestSection {
+    async fn document(&self, ctx: &Context<'_>) -> Result<Option<Document>> {
+        let loader = ctx.data_unchecked::<DataLoader<IkigaiDataLoader>>();
+        let document = loader.load_one(DocumentById(self.document_id)).await?;
+        Ok(document)
+    }
+
+    async fn assignment(&self, ctx: &Context<'_>) -> Result<Option<Assignment>> {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        Assignment::find_by_document(&mut conn, self.document_id).format_err()
+    }
+}
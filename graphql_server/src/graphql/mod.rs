@@ -18,6 +18,7 @@ pub mod file_action;
 pub mod ikigai_mutation;
 pub mod ikigai_query;
 pub mod ikigai_subscription;
+pub mod mock_test_action;
 pub mod notification_center;
 pub mod space_action;
 pub mod user_action;
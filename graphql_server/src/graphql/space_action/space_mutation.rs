@@ -259,4 +259,26 @@ impl SpaceMutation {
 
         Ok(true)
     }
+
+    async fn space_export_gradebook(
+        &self,
+        ctx: &Context<'_>,
+        space_id: i32,
+        format: ExportFormat,
+    ) -> Result<Option<String>> {
+        space_quick_authorize(ctx, space_id, SpaceActionPermission::ManageSpaceContent).await?;
+        let user_id = get_user_id_from_ctx(ctx).await?;
+
+        let (space, data) = {
+            let mut conn = get_conn_from_ctx(ctx).await?;
+            let space = Space::find_by_id(&mut conn, space_id).format_err()?;
+            let data = build_gradebook(&mut conn, space_id)
+                .and_then(|gradebook| build_gradebook_export(&mut conn, &gradebook))
+                .and_then(|table| table.to_bytes(format))
+                .format_err()?;
+            (space, data)
+        };
+        let file_name = format!("{} - Gradebook", space.name);
+        upload_export_file(ctx, user_id, &file_name, format, data).await
+    }
 }
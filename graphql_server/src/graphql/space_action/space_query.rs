@@ -4,7 +4,8 @@ use async_graphql::*;
 use crate::db::*;
 use crate::error::IkigaiErrorExt;
 use crate::helper::{
-    get_conn_from_ctx, get_space_allowed_permissions, get_user_id_from_ctx, space_quick_authorize,
+    build_gradebook, get_conn_from_ctx, get_space_allowed_permissions, get_user_id_from_ctx,
+    space_quick_authorize, Gradebook,
 };
 
 #[derive(Default)]
@@ -43,4 +44,16 @@ impl SpaceQuery {
         let mut conn = get_conn_from_ctx(ctx).await?;
         SpaceInviteToken::find_all_by_spaces(&mut conn, space_id).format_err()
     }
+
+    async fn space_gradebook(&self, ctx: &Context<'_>, space_id: i32) -> Result<Gradebook> {
+        space_quick_authorize(ctx, space_id, SpaceActionPermission::ManageSpaceContent).await?;
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        build_gradebook(&mut conn, space_id).format_err()
+    }
+
+    async fn space_get_rubrics(&self, ctx: &Context<'_>, space_id: i32) -> Result<Vec<Rubric>> {
+        space_quick_authorize(ctx, space_id, SpaceActionPermission::ManageSpaceContent).await?;
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        Rubric::find_all_by_space(&mut conn, space_id).format_err()
+    }
 }
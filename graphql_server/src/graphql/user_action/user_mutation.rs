@@ -3,13 +3,14 @@ use diesel::Connection;
 use uuid::Uuid;
 
 use crate::authentication_token::Claims;
-use crate::authorization::RubricActionPermission;
+use crate::authorization::{RubricActionPermission, SpaceActionPermission};
 use crate::db::*;
 use crate::error::{IkigaiError, IkigaiErrorExt};
 use crate::graphql::validator::Email;
 use crate::helper::{
     create_default_space, get_conn_from_ctx, get_user_from_ctx, get_user_id_from_ctx,
-    rubric_quick_authorize, send_space_magic_link,
+    import_rubric_data, rubric_quick_authorize, send_space_magic_link, space_quick_authorize,
+    upsert_rubric_with_version, RubricFileFormat,
 };
 use crate::service::google::verify_google_id_token;
 use crate::service::redis::Redis;
@@ -90,11 +91,14 @@ impl UserMutation {
             )
             .await?;
         }
+        if let Some(space_id) = rubric.space_id {
+            space_quick_authorize(ctx, space_id, SpaceActionPermission::ManageSpaceContent).await?;
+        }
 
         let user_id = get_user_id_from_ctx(ctx).await?;
         let mut conn = get_conn_from_ctx(ctx).await?;
         rubric.user_id = user_id;
-        let rubric = Rubric::upsert(&mut conn, rubric).format_err()?;
+        let rubric = upsert_rubric_with_version(&mut conn, rubric).format_err()?;
 
         Ok(rubric)
     }
@@ -107,6 +111,38 @@ impl UserMutation {
 
         Ok(true)
     }
+
+    async fn user_import_rubric(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+        format: RubricFileFormat,
+        content: String,
+        #[graphql(default)] rubric_type: RubricType,
+        space_id: Option<i32>,
+    ) -> Result<Rubric> {
+        if let Some(space_id) = space_id {
+            space_quick_authorize(ctx, space_id, SpaceActionPermission::ManageSpaceContent).await?;
+        }
+        let data = import_rubric_data(&content, format, rubric_type).format_err()?;
+
+        let user_id = get_user_id_from_ctx(ctx).await?;
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let mut rubric = Rubric::new(user_id, name, data);
+        rubric.space_id = space_id;
+        upsert_rubric_with_version(&mut conn, rubric).format_err()
+    }
+
+    async fn user_clone_rubric_template(
+        &self,
+        ctx: &Context<'_>,
+        template: RubricTemplate,
+    ) -> Result<Rubric> {
+        let user_id = get_user_id_from_ctx(ctx).await?;
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let rubric = Rubric::new(user_id, template.name().into(), template.data());
+        upsert_rubric_with_version(&mut conn, rubric).format_err()
+    }
 }
 
 async fn init_space_for_user_by_email(
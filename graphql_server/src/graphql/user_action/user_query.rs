@@ -1,9 +1,9 @@
-use crate::authorization::DocumentActionPermission;
+use crate::authorization::{DocumentActionPermission, RubricActionPermission};
 use crate::db::*;
 use crate::error::{IkigaiError, IkigaiErrorExt};
 use crate::helper::{
-    create_default_space, document_authorize, get_conn_from_ctx, get_user_from_ctx,
-    get_user_id_from_ctx,
+    create_default_space, document_authorize, export_rubric_data, get_conn_from_ctx,
+    get_user_from_ctx, get_user_id_from_ctx, rubric_quick_authorize, RubricFileFormat,
 };
 use async_graphql::*;
 use diesel::Connection;
@@ -67,6 +67,35 @@ impl UserQuery {
         Rubric::find_all_by_user(&mut conn, user_id).format_err()
     }
 
+    async fn user_get_rubric_versions(
+        &self,
+        ctx: &Context<'_>,
+        rubric_id: Uuid,
+    ) -> Result<Vec<RubricVersion>> {
+        rubric_quick_authorize(ctx, rubric_id, RubricActionPermission::ViewRubric).await?;
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        RubricVersion::find_all_by_rubric(&mut conn, rubric_id).format_err()
+    }
+
+    async fn user_export_rubric(
+        &self,
+        ctx: &Context<'_>,
+        rubric_id: Uuid,
+        format: RubricFileFormat,
+    ) -> Result<String> {
+        rubric_quick_authorize(ctx, rubric_id, RubricActionPermission::ViewRubric).await?;
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        let rubric = Rubric::find_by_id(&mut conn, rubric_id).format_err()?;
+        export_rubric_data(&rubric.data, format).format_err()
+    }
+
+    async fn user_get_rubric_templates(&self) -> Vec<RubricTemplateItem> {
+        RubricTemplate::all()
+            .into_iter()
+            .map(RubricTemplateItem::from)
+            .collect()
+    }
+
     async fn user_check_document(
         &self,
         ctx: &Context<'_>,
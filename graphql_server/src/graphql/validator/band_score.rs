@@ -0,0 +1,15 @@
+use async_graphql::{CustomValidator, InputValueError};
+
+use crate::db::NewBandScore;
+
+pub struct ValidBandScore;
+
+impl CustomValidator<NewBandScore> for ValidBandScore {
+    fn check(&self, band_score: &NewBandScore) -> Result<(), InputValueError<NewBandScore>> {
+        if band_score.name.trim().is_empty() {
+            return Err(InputValueError::custom("Band score name is required"));
+        }
+
+        band_score.range.check().map_err(InputValueError::custom)
+    }
+}
@@ -1,3 +1,5 @@
+pub mod band_score;
 pub mod id;
 
+pub use band_score::*;
 pub use id::*;
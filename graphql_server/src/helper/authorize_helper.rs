@@ -7,8 +7,8 @@ use uuid::Uuid;
 
 use crate::authentication_token::{ActiveSpaceId, Claims};
 use crate::authorization::{
-    DocumentActionPermission, DocumentAuth, RubricActionPermission, RubricAuth,
-    SpaceActionPermission, SpaceAuth, UserAuth,
+    BandScoreActionPermission, BandScoreAuth, DocumentActionPermission, DocumentAuth,
+    RubricActionPermission, RubricAuth, SpaceActionPermission, SpaceAuth, UserAuth,
 };
 use crate::connection_pool::get_conn_from_actor;
 use crate::db::*;
@@ -275,11 +275,51 @@ pub async fn rubric_is_allowed(
     action: RubricActionPermission,
 ) -> Result<bool> {
     let oso = ctx.data::<Oso>()?;
-    let user_auth = get_user_auth_by_user_id_from_ctx(ctx, user_id, None).await?;
-    let mut conn = get_conn_from_ctx(ctx).await?;
-    let rubric = Rubric::find_by_id(&mut conn, rubric_id)?;
+    let rubric = {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        Rubric::find_by_id(&mut conn, rubric_id)?
+    };
+    // Creator is checked within active space, shared rubric within its space
+    let relative_space_id = rubric.space_id.filter(|_| rubric.user_id != user_id);
+    let user_auth = get_user_auth_by_user_id_from_ctx(ctx, user_id, relative_space_id).await?;
     let rubric_auth = RubricAuth::new(&rubric);
     let is_allowed = oso.is_allowed(user_auth, action.to_string(), rubric_auth)?;
 
     Ok(is_allowed)
 }
+
+pub async fn band_score_quick_authorize(
+    ctx: &Context<'_>,
+    band_score_id: i32,
+    action: BandScoreActionPermission,
+) -> Result<()> {
+    let user_id = get_user_id_from_ctx(ctx).await?;
+    let is_allow = band_score_is_allowed(ctx, user_id, band_score_id, action).await?;
+
+    if !is_allow {
+        return Err(IkigaiError::new_unauthorized(
+            "You don't have permission to do action in this band score",
+        ))
+        .format_err();
+    }
+
+    Ok(())
+}
+
+pub async fn band_score_is_allowed(
+    ctx: &Context<'_>,
+    user_id: i32,
+    band_score_id: i32,
+    action: BandScoreActionPermission,
+) -> Result<bool> {
+    let oso = ctx.data::<Oso>()?;
+    let band_score = {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        BandScore::find(&mut conn, band_score_id).format_err()?
+    };
+    let user_auth = get_user_auth_by_user_id_from_ctx(ctx, user_id, band_score.space_id).await?;
+    let band_score_auth = BandScoreAuth::new(&band_score);
+    let is_allowed = oso.is_allowed(user_auth, action.to_string(), band_score_auth)?;
+
+    Ok(is_allowed)
+}
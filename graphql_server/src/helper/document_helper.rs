@@ -1,4 +1,5 @@
 use diesel::PgConnection;
+use std::collections::HashMap;
 use uuid::Uuid;
 
 use crate::db::Document;
@@ -23,6 +24,9 @@ pub struct DocumentCloneConfig {
     pub clone_to_replace_document_id: Option<Uuid>,
     #[builder(default = "true")]
     pub keep_document_type: bool,
+    // Submission documents are graded against answer keys of assignment, so they do not keep a copy
+    #[builder(default = "true")]
+    pub keep_answer_keys: bool,
 }
 
 impl Document {
@@ -57,10 +61,11 @@ impl Document {
             Document::upsert(conn, new_doc)?
         };
 
-        // Step 1: Clone pages of document
+        // Step 1: Clone pages of document, keep new id of every cloned item by its old id
+        let mut id_map = HashMap::new();
         let pages = Page::find_all_by_document_id(conn, self.id)?;
         for page in pages {
-            page.deep_clone(conn, &new_document)?;
+            page.deep_clone(conn, &new_document, config.keep_answer_keys, &mut id_map)?;
         }
 
         // Step 2: Document Type
@@ -72,9 +77,18 @@ impl Document {
             }
 
             if let Ok(Some(submission)) = Submission::find_by_document(conn, self.id) {
+                let submission_id = submission.id;
                 let mut new_submission = NewSubmission::from(submission);
                 new_submission.document_id = new_document.id;
-                Submission::insert(conn, new_submission)?;
+                let new_submission = Submission::insert(conn, new_submission)?;
+
+                let annotations = FeedbackAnnotation::find_all_by_submission(conn, submission_id)?
+                    .into_iter()
+                    .filter_map(|annotation| {
+                        annotation.clone_with_id_map(new_submission.id, &id_map)
+                    })
+                    .collect();
+                FeedbackAnnotation::insert_many(conn, annotations)?;
             }
         }
 
@@ -108,6 +122,8 @@ impl Page {
         &self,
         conn: &mut PgConnection,
         new_document: &Document,
+        keep_answer_keys: bool,
+        id_map: &mut HashMap<Uuid, Uuid>,
     ) -> Result<Self, IkigaiError> {
         let mut this = self.clone();
         this.id = Uuid::new_v4();
@@ -120,7 +136,13 @@ impl Page {
 
         let page_contents = PageContent::find_all_by_page(conn, self.id)?;
         for page_content in page_contents {
-            page_content.deep_clone(conn, &new_page, new_document.creator_id)?;
+            page_content.deep_clone(
+                conn,
+                &new_page,
+                new_document.creator_id,
+                keep_answer_keys,
+                id_map,
+            )?;
         }
 
         Ok(new_page)
@@ -133,10 +155,13 @@ impl PageContent {
         conn: &mut PgConnection,
         new_page: &Page,
         creator_id: i32,
+        keep_answer_keys: bool,
+        id_map: &mut HashMap<Uuid, Uuid>,
     ) -> Result<Self, IkigaiError> {
         let new_content =
             PageContent::new(Uuid::new_v4(), new_page.id, self.index, self.body.clone());
         let mut new_page_content = PageContent::upsert(conn, new_content)?;
+        id_map.insert(self.id, new_page_content.id);
 
         let mut new_content = new_page_content.get_json_content();
         let writing_blocks = WritingBlock::find_all_by_page_content(conn, self.id)?;
@@ -144,6 +169,7 @@ impl PageContent {
             if let Ok(new_writing_block) =
                 writing_block.deep_clone(conn, &new_page_content, creator_id)
             {
+                id_map.insert(writing_block.id, new_writing_block.id);
                 new_content.replace_block_id(
                     "writingBlock",
                     "writingBlockId",
@@ -153,11 +179,45 @@ impl PageContent {
             }
         }
 
+        let quiz_blocks = QuizBlock::find_all_by_page_content(conn, self.id)?;
+        for quiz_block in quiz_blocks {
+            if let Ok(new_quiz_block) =
+                quiz_block.deep_clone(conn, &new_page_content, creator_id, keep_answer_keys)
+            {
+                new_content.replace_block_id(
+                    "quizBlock",
+                    "quizBlockId",
+                    &serde_json::to_value(quiz_block.id).unwrap_or_default(),
+                    &serde_json::to_value(new_quiz_block.id).unwrap_or_default(),
+                );
+            }
+        }
+
         new_page_content.body = serde_json::to_value(new_content).unwrap_or_default();
         Ok(PageContent::upsert(conn, new_page_content)?)
     }
 }
 
+impl FeedbackAnnotation {
+    // None if anchored content is not cloned
+    pub fn clone_with_id_map(
+        &self,
+        new_submission_id: i32,
+        id_map: &HashMap<Uuid, Uuid>,
+    ) -> Option<Self> {
+        let mut new_annotation = self.clone();
+        new_annotation.id = Uuid::new_v4();
+        new_annotation.submission_id = new_submission_id;
+        new_annotation.page_content_id = *id_map.get(&self.page_content_id)?;
+        if let Some(writing_block_id) = self.writing_block_id {
+            new_annotation.writing_block_id = Some(*id_map.get(&writing_block_id)?);
+        }
+        new_annotation.updated_at = get_now_as_secs();
+        new_annotation.created_at = get_now_as_secs();
+        Some(new_annotation)
+    }
+}
+
 impl WritingBlock {
     pub fn deep_clone(
         &self,
@@ -173,6 +233,33 @@ impl WritingBlock {
     }
 }
 
+impl QuizBlock {
+    pub fn deep_clone(
+        &self,
+        conn: &mut PgConnection,
+        new_page_content: &PageContent,
+        creator_id: i32,
+        keep_answer_keys: bool,
+    ) -> Result<Self, IkigaiError> {
+        let mut new_quiz_block = self.clone();
+        new_quiz_block.id = Uuid::new_v4();
+        new_quiz_block.page_content_id = new_page_content.id;
+        new_quiz_block.creator_id = creator_id;
+        new_quiz_block.original_quiz_block_id = Some(self.id);
+        let new_quiz_block = QuizBlock::upsert(conn, new_quiz_block)?;
+
+        if !keep_answer_keys {
+            return Ok(new_quiz_block);
+        }
+        if let Some(answer_key) = QuizAnswerKey::find_opt(conn, self.id)? {
+            let new_answer_key = QuizAnswerKey::new(new_quiz_block.id, answer_key.answer);
+            QuizAnswerKey::upsert(conn, new_answer_key)?;
+        }
+
+        Ok(new_quiz_block)
+    }
+}
+
 pub fn get_all_documents_by_id(
     conn: &mut PgConnection,
     document_id: Uuid,
@@ -0,0 +1,296 @@
+use async_graphql::*;
+use aws_sdk_s3::types::ByteStream;
+use diesel::PgConnection;
+use rust_xlsxwriter::Workbook;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::db::*;
+use crate::error::{IkigaiError, IkigaiErrorExt};
+use crate::helper::{generate_download_url, get_conn_from_ctx, Gradebook};
+use crate::service::Storage;
+use crate::util::get_date_from_ts;
+
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Xlsx,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &str {
+        match self {
+            Self::Csv => "csv",
+            Self::Xlsx => "xlsx",
+        }
+    }
+
+    pub fn content_type(&self) -> &str {
+        match self {
+            Self::Csv => "text/csv",
+            Self::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ExportCell {
+    Text(String),
+    Number(f64),
+    Empty,
+}
+
+impl From<String> for ExportCell {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<Option<f64>> for ExportCell {
+    fn from(value: Option<f64>) -> Self {
+        value.map_or(Self::Empty, Self::Number)
+    }
+}
+
+impl From<Option<i64>> for ExportCell {
+    fn from(value: Option<i64>) -> Self {
+        value.map_or(Self::Empty, |ts| {
+            Self::Text(
+                get_date_from_ts(ts)
+                    .format("%Y-%m-%d %H:%M:%S UTC")
+                    .to_string(),
+            )
+        })
+    }
+}
+
+impl ExportCell {
+    fn to_text(&self) -> String {
+        match self {
+            Self::Text(text) => text.clone(),
+            Self::Number(number) => number.to_string(),
+            Self::Empty => String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ExportTable {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<ExportCell>>,
+}
+
+impl ExportTable {
+    pub fn to_bytes(&self, format: ExportFormat) -> Result<Vec<u8>, IkigaiError> {
+        match format {
+            ExportFormat::Csv => self.to_csv(),
+            ExportFormat::Xlsx => self.to_xlsx(),
+        }
+    }
+
+    fn to_csv(&self) -> Result<Vec<u8>, IkigaiError> {
+        let mut writer = csv::Writer::from_writer(vec![]);
+        writer.write_record(&self.headers)?;
+        for row in &self.rows {
+            writer.write_record(row.iter().map(|cell| cell.to_text()))?;
+        }
+        csv_writer_into_string(writer).map(String::into_bytes)
+    }
+
+    fn to_xlsx(&self) -> Result<Vec<u8>, IkigaiError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        for (col, header) in self.headers.iter().enumerate() {
+            worksheet.write_string(0, col as u16, header)?;
+        }
+        for (row_index, row) in self.rows.iter().enumerate() {
+            let row_index = row_index as u32 + 1;
+            for (col, cell) in row.iter().enumerate() {
+                match cell {
+                    ExportCell::Text(text) => {
+                        worksheet.write_string(row_index, col as u16, text)?;
+                    }
+                    ExportCell::Number(number) => {
+                        worksheet.write_number(row_index, col as u16, *number)?;
+                    }
+                    ExportCell::Empty => {}
+                }
+            }
+        }
+        Ok(workbook.save_to_buffer()?)
+    }
+}
+
+pub fn build_assignment_export(
+    conn: &mut PgConnection,
+    assignment: &Assignment,
+) -> Result<ExportTable, IkigaiError> {
+    let mut submissions = Submission::find_all_by_assignment(conn, assignment.id)?;
+    let user_ids = submissions
+        .iter()
+        .map(|submission| submission.user_id)
+        .collect();
+    let users: HashMap<i32, User> = User::find_by_ids(conn, &user_ids)?
+        .into_iter()
+        .map(|user| (user.id, user))
+        .collect();
+    let student_name = |submission: &Submission| {
+        if submission.is_anonymous(assignment) {
+            return submission.pseudonym();
+        }
+        users
+            .get(&submission.user_id)
+            .map(|user| user.name())
+            .unwrap_or_default()
+    };
+    submissions.sort_by(|a, b| {
+        (student_name(a), a.anonymous_id.as_str(), a.attempt_number).cmp(&(
+            student_name(b),
+            b.anonymous_id.as_str(),
+            b.attempt_number,
+        ))
+    });
+
+    let submission_ids: Vec<i32> = submissions.iter().map(|submission| submission.id).collect();
+    let rubric_submissions: HashMap<i32, RubricSubmission> =
+        RubricSubmission::find_all_by_submissions(conn, &submission_ids)?
+            .into_iter()
+            .map(|item| (item.submission_id, item))
+            .collect();
+
+    // Criteria columns follow the rubric of assignment, or the first graded rubric
+    let criteria = if let Some(rubric_id) = assignment.grade_by_rubric_id {
+        Rubric::find_by_id(conn, rubric_id)?.data.criteria
+    } else {
+        rubric_submissions
+            .values()
+            .next()
+            .map(|item| item.graded_data.criteria.clone())
+            .unwrap_or_default()
+    };
+
+    let mut headers: Vec<String> = vec![
+        "Student".into(),
+        "Email".into(),
+        "Attempt".into(),
+        "Submit At".into(),
+        "Late By (Seconds)".into(),
+        "Auto Grade".into(),
+        "Final Grade".into(),
+    ];
+    headers.append(&mut criteria.clone());
+
+    let rows = submissions
+        .iter()
+        .map(|submission| {
+            let email = if submission.is_anonymous(assignment) {
+                None
+            } else {
+                users
+                    .get(&submission.user_id)
+                    .map(|user| user.email.clone())
+            };
+            let mut row = vec![
+                ExportCell::from(student_name(submission)),
+                ExportCell::from(email.unwrap_or_default()),
+                ExportCell::Number(submission.attempt_number as f64),
+                ExportCell::from(submission.submit_at),
+                ExportCell::Number(submission.late_by as f64),
+                ExportCell::from(submission.auto_grade),
+                ExportCell::from(submission.final_grade),
+            ];
+            let graded_data = rubric_submissions
+                .get(&submission.id)
+                .map(|item| &item.graded_data);
+            for criteria_name in &criteria {
+                let score = graded_data.and_then(|data| {
+                    data.criteria
+                        .iter()
+                        .position(|name| name == criteria_name)
+                        .and_then(|index| data.criteria_score(index))
+                });
+                row.push(ExportCell::from(score));
+            }
+            row
+        })
+        .collect();
+
+    Ok(ExportTable { headers, rows })
+}
+
+pub fn build_gradebook_export(
+    conn: &mut PgConnection,
+    gradebook: &Gradebook,
+) -> Result<ExportTable, IkigaiError> {
+    let document_ids = gradebook
+        .assignments
+        .iter()
+        .map(|assignment| assignment.document_id)
+        .collect();
+    let titles: HashMap<Uuid, String> = Document::find_by_ids(conn, document_ids)?
+        .into_iter()
+        .map(|document| (document.id, document.title))
+        .collect();
+
+    let mut headers: Vec<String> = vec!["Student".into(), "Email".into()];
+    for assignment in &gradebook.assignments {
+        headers.push(
+            titles
+                .get(&assignment.document_id)
+                .cloned()
+                .unwrap_or_default(),
+        );
+    }
+
+    let rows = gradebook
+        .rows
+        .iter()
+        .map(|row| {
+            let mut cells = vec![
+                ExportCell::from(format!("{} {}", row.user.first_name, row.user.last_name)),
+                ExportCell::from(row.user.email.clone()),
+            ];
+            for cell in &row.cells {
+                cells.push(ExportCell::from(cell.final_grade));
+            }
+            cells
+        })
+        .collect();
+
+    Ok(ExportTable { headers, rows })
+}
+
+// Upload exported file as private file of user and return presigned download url
+pub async fn upload_export_file(
+    ctx: &Context<'_>,
+    user_id: i32,
+    file_name: &str,
+    format: ExportFormat,
+    data: Vec<u8>,
+) -> Result<Option<String>> {
+    let mut file = File::new(
+        user_id,
+        false,
+        format!("{file_name}.{}", format.extension()),
+        format.content_type().into(),
+        data.len() as i64,
+    );
+    Storage::from_env_config()
+        .upload_bytes(&file.key(), format.content_type(), ByteStream::from(data))
+        .await
+        .format_err()?;
+
+    file.status = FileStatus::Success;
+    let file = {
+        let mut conn = get_conn_from_ctx(ctx).await?;
+        File::upsert(&mut conn, &file).format_err()?
+    };
+    generate_download_url(&file, ctx).await
+}
+
+pub fn csv_writer_into_string(writer: csv::Writer<Vec<u8>>) -> Result<String, IkigaiError> {
+    let content = writer.into_inner().map_err(|e| {
+        IkigaiError::from(std::io::Error::new(e.error().kind(), e.error().to_string()))
+    })?;
+    String::from_utf8(content).map_err(|_| IkigaiError::InternalServerError)
+}
@@ -0,0 +1,102 @@
+use diesel::PgConnection;
+use std::collections::HashMap;
+
+use crate::db::*;
+use crate::error::IkigaiError;
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct GradebookCell {
+    pub assignment_id: i32,
+    // Status of last attempt, None if student has not started yet
+    pub status: Option<SubmissionStatus>,
+    pub number_of_attempt: i32,
+    pub last_submission_id: Option<i32>,
+    // Calculated by attempt scoring policy of assignment
+    pub final_grade: Option<f64>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct GradebookRow {
+    pub user: PublicUser,
+    // Same order with assignments of gradebook
+    pub cells: Vec<GradebookCell>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct Gradebook {
+    pub assignments: Vec<Assignment>,
+    pub rows: Vec<GradebookRow>,
+}
+
+pub fn build_gradebook(conn: &mut PgConnection, space_id: i32) -> Result<Gradebook, IkigaiError> {
+    let student_ids =
+        SpaceMember::find_all_space_members_by_role_and_class(conn, space_id, Role::Student)?
+            .into_iter()
+            .map(|member| member.user_id)
+            .collect();
+    let mut students: Vec<PublicUser> = User::find_by_ids(conn, &student_ids)?
+        .into_iter()
+        .map(PublicUser::from)
+        .collect();
+    students.sort_by(|a, b| {
+        (&a.first_name, &a.last_name, a.id).cmp(&(&b.first_name, &b.last_name, b.id))
+    });
+
+    let document_ids = Document::find_all_by_space(conn, space_id, false)?
+        .into_iter()
+        .map(|document| document.id)
+        .collect();
+    let mut assignments = Assignment::find_all_by_documents(conn, &document_ids)?;
+    assignments.sort_by_key(|assignment| (assignment.created_at, assignment.id));
+
+    let assignment_ids = assignments.iter().map(|assignment| assignment.id).collect();
+    let mut submissions_by_cell: HashMap<(i32, i32), Vec<Submission>> = HashMap::new();
+    for submission in Submission::find_all_by_assignments(conn, assignment_ids)? {
+        submissions_by_cell
+            .entry((submission.user_id, submission.assignment_id))
+            .or_default()
+            .push(submission);
+    }
+
+    let rows = students
+        .into_iter()
+        .map(|student| {
+            let cells = assignments
+                .iter()
+                .map(|assignment| {
+                    let submissions = submissions_by_cell
+                        .get(&(student.id, assignment.id))
+                        .map(|submissions| submissions.as_slice())
+                        .unwrap_or_default();
+                    let last_submission = submissions
+                        .iter()
+                        .max_by_key(|submission| submission.attempt_number);
+                    // Anonymous submissions must not be linked to student before release
+                    let is_anonymous = submissions
+                        .iter()
+                        .any(|submission| submission.is_anonymous(assignment));
+                    GradebookCell {
+                        assignment_id: assignment.id,
+                        status: last_submission
+                            .map(|submission| submission.submission_status(true)),
+                        number_of_attempt: submissions.len() as i32,
+                        last_submission_id: last_submission
+                            .filter(|_| !is_anonymous)
+                            .map(|submission| submission.id),
+                        final_grade: if is_anonymous {
+                            None
+                        } else {
+                            assignment.calculate_effective_grade(submissions)
+                        },
+                    }
+                })
+                .collect();
+            GradebookRow {
+                user: student,
+                cells,
+            }
+        })
+        .collect();
+
+    Ok(Gradebook { assignments, rows })
+}
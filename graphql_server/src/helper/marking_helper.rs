@@ -0,0 +1,91 @@
+use diesel::PgConnection;
+use std::collections::HashMap;
+
+use crate::db::*;
+use crate::error::IkigaiError;
+use crate::helper::find_grading_rubric;
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct MarkingSummary {
+    pub submission_id: i32,
+    pub markings: Vec<SubmissionMarking>,
+    // Difference between highest and lowest marking, None if marked less than twice
+    pub spread: Option<f64>,
+    pub has_discrepancy: bool,
+}
+
+impl MarkingSummary {
+    pub fn new(
+        submission_id: i32,
+        markings: Vec<SubmissionMarking>,
+        assignment: &Assignment,
+    ) -> Self {
+        let spread = SubmissionMarking::spread(&markings);
+        Self {
+            submission_id,
+            markings,
+            spread,
+            has_discrepancy: spread.is_some_and(|spread| spread > assignment.discrepancy_threshold),
+        }
+    }
+}
+
+pub fn build_marking_summaries(
+    conn: &mut PgConnection,
+    assignment: &Assignment,
+) -> Result<Vec<MarkingSummary>, IkigaiError> {
+    let submission_ids: Vec<i32> = Submission::find_all_by_assignment(conn, assignment.id)?
+        .into_iter()
+        .filter(|submission| submission.submit_at.is_some())
+        .map(|submission| submission.id)
+        .collect();
+    let mut markings_by_submission: HashMap<i32, Vec<SubmissionMarking>> = HashMap::new();
+    for marking in SubmissionMarking::find_all_by_submissions(conn, &submission_ids)? {
+        markings_by_submission
+            .entry(marking.submission_id)
+            .or_default()
+            .push(marking);
+    }
+
+    let summaries = submission_ids
+        .into_iter()
+        .map(|submission_id| {
+            let markings = markings_by_submission
+                .remove(&submission_id)
+                .unwrap_or_default();
+            MarkingSummary::new(submission_id, markings, assignment)
+        })
+        .collect();
+    Ok(summaries)
+}
+
+// Rubric marking is validated like rubric grading and its grade is the total rubric score
+pub fn upsert_marking(
+    conn: &mut PgConnection,
+    submission: &Submission,
+    assignment: &Assignment,
+    mut marking: SubmissionMarking,
+) -> Result<SubmissionMarking, IkigaiError> {
+    if !assignment.double_marking {
+        return Err(IkigaiError::new_bad_request(
+            "Assignment is not double marked",
+        ));
+    }
+    if submission.submit_at.is_none() {
+        return Err(IkigaiError::new_bad_request(
+            "Submission is not submitted yet",
+        ));
+    }
+
+    if let Some(graded_data) = &marking.graded_data {
+        let rubric_submission = RubricSubmission::find_by_submission_opt(conn, submission.id)?;
+        let (_, _, rubric_data) =
+            find_grading_rubric(conn, assignment, rubric_submission.as_ref())?;
+        graded_data
+            .validate_grading(&rubric_data)
+            .map_err(IkigaiError::new_bad_request)?;
+        marking.grade = graded_data.total_rubric_score();
+    }
+
+    Ok(SubmissionMarking::upsert(conn, marking)?)
+}
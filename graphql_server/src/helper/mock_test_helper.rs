@@ -0,0 +1,114 @@
+use diesel::PgConnection;
+
+use crate::db::*;
+use crate::error::IkigaiError;
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct MockTestSectionResult {
+    pub skill: MockTestSkill,
+    pub assignment_id: Option<i32>,
+    // Status of last attempt, None if student has not started yet
+    pub status: Option<SubmissionStatus>,
+    // Calculated by band score and attempt scoring policy of assignment
+    pub band: Option<f64>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct MockTestProgress {
+    pub user_id: i32,
+    pub sections: Vec<MockTestSectionResult>,
+    pub number_of_completed_section: i32,
+    // Only available when all four skills have a band
+    pub overall_band: Option<f64>,
+}
+
+pub fn build_mock_test_progress(
+    conn: &mut PgConnection,
+    mock_test_id: i32,
+    user_id: i32,
+    include_hidden_grade: bool,
+) -> Result<MockTestProgress, IkigaiError> {
+    let mut sections = vec![];
+    for section in MockTestSection::find_all_by_mock_test(conn, mock_test_id)? {
+        let assignment = Assignment::find_by_document(conn, section.document_id)?;
+        let result = if let Some(assignment) = assignment {
+            let submissions =
+                Submission::find_all_by_assignment_and_user(conn, user_id, assignment.id)?;
+            let last_submission = submissions
+                .iter()
+                .max_by_key(|submission| submission.attempt_number);
+            let visible_submissions: Vec<Submission> = submissions
+                .iter()
+                .filter(|submission| {
+                    include_hidden_grade || submission.allow_for_student_view_answer
+                })
+                .cloned()
+                .collect();
+            // Raw score must not be averaged as a band if grading of assignment changed
+            let band = if is_band_graded(conn, &assignment)? {
+                assignment.calculate_effective_grade(&visible_submissions)
+            } else {
+                None
+            };
+            MockTestSectionResult {
+                skill: section.skill,
+                assignment_id: Some(assignment.id),
+                status: last_submission
+                    .map(|submission| submission.submission_status(include_hidden_grade)),
+                band,
+            }
+        } else {
+            MockTestSectionResult {
+                skill: section.skill,
+                assignment_id: None,
+                status: None,
+                band: None,
+            }
+        };
+        sections.push(result);
+    }
+
+    let number_of_completed_section = sections
+        .iter()
+        .filter(|section| {
+            section
+                .status
+                .is_some_and(|status| status != SubmissionStatus::InDoing)
+        })
+        .count() as i32;
+    let bands: Vec<f64> = sections.iter().filter_map(|section| section.band).collect();
+    let overall_band = if bands.len() == MockTestSkill::all().len() {
+        Some(round_ielts_band(
+            bands.iter().sum::<f64>() / bands.len() as f64,
+        ))
+    } else {
+        None
+    };
+
+    Ok(MockTestProgress {
+        user_id,
+        sections,
+        number_of_completed_section,
+        overall_band,
+    })
+}
+
+// Auto grade is converted to band by band score, rubric grade is a band if rubric averages IELTS bands
+pub fn is_band_graded(
+    conn: &mut PgConnection,
+    assignment: &Assignment,
+) -> Result<bool, IkigaiError> {
+    let is_band_graded = match assignment.grade_method {
+        GradeMethod::Auto => assignment.band_score_id.is_some(),
+        GradeMethod::Rubric => {
+            if let Some(rubric_id) = assignment.grade_by_rubric_id {
+                let rubric = Rubric::find_by_id(conn, rubric_id)?;
+                rubric.data.rubric_type == RubricType::IeltsBandAverage
+            } else {
+                false
+            }
+        }
+        GradeMethod::Manual => false,
+    };
+    Ok(is_band_graded)
+}
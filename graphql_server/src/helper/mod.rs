@@ -1,9 +1,21 @@
 pub mod authorize_helper;
 pub mod document_helper;
+pub mod export_helper;
+pub mod gradebook_helper;
+pub mod marking_helper;
+pub mod mock_test_helper;
+pub mod peer_review_helper;
+pub mod rubric_helper;
 pub mod submission_helper;
 
 pub use authorize_helper::*;
 pub use document_helper::*;
+pub use export_helper::*;
+pub use gradebook_helper::*;
+pub use marking_helper::*;
+pub use mock_test_helper::*;
+pub use peer_review_helper::*;
+pub use rubric_helper::*;
 pub use submission_helper::*;
 
 use async_graphql::dataloader::DataLoader;
@@ -11,9 +23,12 @@ use async_graphql::*;
 use diesel::{Connection as DieselConnection, PgConnection};
 use uuid::Uuid;
 
+use crate::connection_pool::get_conn_from_actor;
 use crate::db::*;
 use crate::error::{IkigaiError, IkigaiErrorExt};
-use crate::graphql::data_loader::{FindPublicUserById, IkigaiDataLoader};
+use crate::graphql::data_loader::{
+    AssignmentById, FindPublicUserById, IkigaiDataLoader, SubmissionByDocumentId,
+};
 use crate::mailer::Mailer;
 use crate::service::redis::Redis;
 use crate::service::Storage;
@@ -29,6 +44,25 @@ pub async fn get_public_user_from_loader(ctx: &Context<'_>, user_id: i32) -> Res
     Ok(user)
 }
 
+// Submission document under anonymous grading must not reveal its student to other users
+pub async fn is_document_identity_hidden(ctx: &Context<'_>, document_id: Uuid) -> Result<bool> {
+    let loader = ctx.data_unchecked::<DataLoader<IkigaiDataLoader>>();
+    let submission = loader.load_one(SubmissionByDocumentId(document_id)).await?;
+    if let Some(submission) = submission {
+        let assignment = loader
+            .load_one(AssignmentById(submission.assignment_id))
+            .await?
+            .ok_or(format!(
+                "Cannot found the assignment {}",
+                submission.assignment_id
+            ))?;
+        let user_id = get_user_id_from_ctx(ctx).await.ok();
+        return Ok(user_id != Some(submission.user_id) && submission.is_anonymous(&assignment));
+    }
+
+    Ok(false)
+}
+
 pub fn duplicate_space(
     conn: &mut PgConnection,
     space_id: i32,
@@ -145,3 +179,39 @@ pub fn create_default_space(conn: &mut PgConnection, user_id: i32) -> Result<Spa
         Ok(space)
     })
 }
+
+// Presets are matched by key. Preset rows seeded before keys existed are matched by name or ranges,
+// then get the key instead of being duplicated.
+pub async fn init_band_score_presets() -> Result<(), IkigaiError> {
+    let mut conn = get_conn_from_actor().await?;
+    let mut unkeyed_presets = vec![];
+    let mut preset_keys = vec![];
+    for band_score in BandScore::find_all_presets(&mut conn)? {
+        if let Some(preset_key) = band_score.preset_key.clone() {
+            preset_keys.push(preset_key);
+        } else {
+            unkeyed_presets.push(band_score);
+        }
+    }
+
+    for preset in NewBandScore::presets() {
+        let preset_key = preset.preset_key.clone().unwrap_or_default();
+        if preset_keys.contains(&preset_key) {
+            continue;
+        }
+
+        let preset_range = serde_json::to_value(&preset.range)?;
+        let unkeyed_index = unkeyed_presets.iter().position(|band_score| {
+            band_score.name == preset.name
+                || serde_json::to_value(&band_score.range).is_ok_and(|range| range == preset_range)
+        });
+        if let Some(unkeyed_index) = unkeyed_index {
+            let band_score = unkeyed_presets.remove(unkeyed_index);
+            BandScore::update_preset(&mut conn, band_score.id, &preset_key, preset.by_percentage)?;
+        } else {
+            BandScore::insert(&mut conn, preset)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use diesel::{Connection, PgConnection};
+use rand::seq::SliceRandom;
+use std::collections::HashMap;
+
+use crate::db::*;
+use crate::error::IkigaiError;
+use crate::helper::find_grading_rubric;
+use crate::util::get_now_as_secs;
+
+// Each latest submitted attempt is reviewed by next students in a shuffled ring,
+// so every student reviews and is reviewed the same number of times.
+// Return number of allocated reviews.
+pub fn allocate_peer_reviews(
+    conn: &mut PgConnection,
+    assignment: &Assignment,
+) -> Result<usize, IkigaiError> {
+    if assignment.peer_review_count <= 0 {
+        return Err(IkigaiError::new_bad_request(
+            "Assignment has no peer review",
+        ));
+    }
+    if assignment.peer_reviews_allocated_at.is_some() {
+        return Err(IkigaiError::new_bad_request(
+            "Peer reviews are already allocated",
+        ));
+    }
+
+    let mut last_submissions: HashMap<i32, Submission> = HashMap::new();
+    for submission in Submission::find_all_by_assignment(conn, assignment.id)? {
+        if submission.submit_at.is_none() {
+            continue;
+        }
+        let has_later_attempt = last_submissions
+            .get(&submission.user_id)
+            .is_some_and(|last| last.attempt_number > submission.attempt_number);
+        if !has_later_attempt {
+            last_submissions.insert(submission.user_id, submission);
+        }
+    }
+    if last_submissions.len() < 2 {
+        return Err(IkigaiError::new_bad_request(
+            "Peer review needs at least 2 submissions",
+        ));
+    }
+    let mut submissions: Vec<Submission> = last_submissions.into_values().collect();
+    submissions.shuffle(&mut rand::thread_rng());
+
+    let reviewer_count = (assignment.peer_review_count as usize).min(submissions.len() - 1);
+    let mut items = vec![];
+    for (index, submission) in submissions.iter().enumerate() {
+        for offset in 1..=reviewer_count {
+            let reviewer = &submissions[(index + offset) % submissions.len()];
+            items.push(PeerReview::new(submission.id, reviewer.user_id));
+        }
+    }
+
+    conn.transaction::<_, IkigaiError, _>(|conn| {
+        let count = PeerReview::insert_many(conn, items)?;
+        Assignment::update_peer_reviews_allocated_at(conn, assignment.id, Some(get_now_as_secs()))?;
+        Ok(count)
+    })
+}
+
+pub fn submit_peer_review(
+    conn: &mut PgConnection,
+    submission: &Submission,
+    assignment: &Assignment,
+    reviewer_id: i32,
+    graded_data: RubricTableData,
+) -> Result<PeerReview, IkigaiError> {
+    let rubric_submission = RubricSubmission::find_by_submission_opt(conn, submission.id)?;
+    let (_, _, rubric_data) = find_grading_rubric(conn, assignment, rubric_submission.as_ref())?;
+    graded_data
+        .validate_grading(&rubric_data)
+        .map_err(IkigaiError::new_bad_request)?;
+
+    Ok(PeerReview::submit(
+        conn,
+        submission.id,
+        reviewer_id,
+        graded_data,
+    )?)
+}
+
+// Blend average peer review score into grade if assignment counts peer reviews
+pub fn apply_peer_review_score(
+    conn: &mut PgConnection,
+    submission: &Submission,
+    assignment: &Assignment,
+    grade: f64,
+) -> Result<f64, IkigaiError> {
+    if assignment.peer_review_count <= 0 || assignment.peer_review_weight <= 0.0 {
+        return Ok(grade);
+    }
+
+    let reviews = PeerReview::find_all_by_submission(conn, submission.id)?;
+    Ok(assignment.apply_peer_review_score(grade, PeerReview::average_score(&reviews)))
+}
@@ -0,0 +1,305 @@
+use diesel::{Connection, PgConnection};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::db::*;
+use crate::error::IkigaiError;
+use crate::helper::{apply_peer_review_score, csv_writer_into_string};
+
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RubricFileFormat {
+    Csv,
+    Json,
+}
+
+// Every change of rubric data creates a new version, so copied rubric submissions keep their structure
+pub fn upsert_rubric_with_version(
+    conn: &mut PgConnection,
+    mut rubric: Rubric,
+) -> Result<Rubric, IkigaiError> {
+    rubric
+        .data
+        .validate_structure()
+        .map_err(IkigaiError::new_bad_request)?;
+
+    conn.transaction::<_, IkigaiError, _>(|conn| {
+        let existing_rubric = Rubric::find_by_id_opt(conn, rubric.id)?;
+        let is_changed = if let Some(existing_rubric) = &existing_rubric {
+            serde_json::to_value(&existing_rubric.data)? != serde_json::to_value(&rubric.data)?
+        } else {
+            true
+        };
+        rubric.version = match &existing_rubric {
+            Some(existing_rubric) if is_changed => existing_rubric.version + 1,
+            Some(existing_rubric) => existing_rubric.version,
+            None => 1,
+        };
+
+        let rubric = Rubric::upsert(conn, rubric)?;
+        if is_changed {
+            RubricVersion::insert(conn, RubricVersion::new(&rubric))?;
+        }
+        Ok(rubric)
+    })
+}
+
+// Rubric which submission is graded against: the version copied when submission started,
+// or current rubric of assignment if submission has no rubric yet.
+// Return rubric id, version and data.
+pub fn find_grading_rubric(
+    conn: &mut PgConnection,
+    assignment: &Assignment,
+    rubric_submission: Option<&RubricSubmission>,
+) -> Result<(Option<Uuid>, Option<i32>, RubricTableData), IkigaiError> {
+    if let Some(rubric_submission) = rubric_submission {
+        let data = if let (Some(rubric_id), Some(version)) = (
+            rubric_submission.rubric_id,
+            rubric_submission.rubric_version,
+        ) {
+            RubricVersion::find(conn, rubric_id, version)?.data
+        } else {
+            // Copied before versioning, the copy itself is the snapshot
+            rubric_submission.graded_data.clone()
+        };
+        return Ok((
+            rubric_submission.rubric_id,
+            rubric_submission.rubric_version,
+            data,
+        ));
+    }
+
+    if let Some(rubric_id) = assignment.grade_by_rubric_id {
+        let rubric = Rubric::find_by_id(conn, rubric_id)?;
+        return Ok((Some(rubric.id), Some(rubric.version), rubric.data));
+    }
+
+    Err(IkigaiError::new_bad_request(
+        "Assignment is not graded by rubric",
+    ))
+}
+
+// Validate graded data against rubric of submission, then save it with total score as final grade.
+// Caller is responsible for the transaction.
+pub fn grade_rubric_submission(
+    conn: &mut PgConnection,
+    submission: &Submission,
+    assignment: &Assignment,
+    mut data: RubricSubmission,
+    actor_id: i32,
+) -> Result<RubricSubmission, IkigaiError> {
+    if assignment.double_marking {
+        return Err(IkigaiError::new_bad_request(
+            "Assignment is double marked, please add a marking instead",
+        ));
+    }
+
+    let existing_item = RubricSubmission::find_by_submission_opt(conn, submission.id)?;
+    let (rubric_id, rubric_version, rubric_data) =
+        find_grading_rubric(conn, assignment, existing_item.as_ref())?;
+    data.graded_data
+        .validate_grading(&rubric_data)
+        .map_err(IkigaiError::new_bad_request)?;
+    data.rubric_id = rubric_id;
+    data.rubric_version = rubric_version;
+
+    let final_grade = apply_peer_review_score(
+        conn,
+        submission,
+        assignment,
+        data.graded_data.total_rubric_score(),
+    )?;
+    let final_grade = assignment.apply_late_penalty(final_grade, submission.late_by);
+    let graded_submission = Submission::update_final_grade(conn, submission.id, final_grade)?;
+    SubmissionGradeHistory::record(
+        conn,
+        submission,
+        &graded_submission,
+        Some(actor_id),
+        GradeChangeSource::Rubric,
+    )?;
+    let item = RubricSubmission::upsert(conn, data)?;
+    Ok(item)
+}
+
+// Move rubric submissions which are not graded yet to the latest version of their rubric.
+// Return number of migrated submissions.
+pub fn migrate_rubric_submissions(
+    conn: &mut PgConnection,
+    assignment_id: i32,
+) -> Result<usize, IkigaiError> {
+    let submission_ids: Vec<i32> = Submission::find_all_by_assignment(conn, assignment_id)?
+        .into_iter()
+        .filter(|submission| submission.feedback_at.is_none())
+        .map(|submission| submission.id)
+        .collect();
+    let rubric_submissions = RubricSubmission::find_all_by_submissions(conn, &submission_ids)?;
+    let rubric_ids: Vec<_> = rubric_submissions
+        .iter()
+        .filter_map(|item| item.rubric_id)
+        .collect();
+    let rubrics: HashMap<_, Rubric> = Rubric::find_all_by_ids(conn, &rubric_ids)?
+        .into_iter()
+        .map(|rubric| (rubric.id, rubric))
+        .collect();
+
+    conn.transaction::<_, IkigaiError, _>(|conn| {
+        let mut count = 0;
+        for mut rubric_submission in rubric_submissions {
+            let rubric = rubric_submission
+                .rubric_id
+                .and_then(|rubric_id| rubrics.get(&rubric_id));
+            if let Some(rubric) = rubric {
+                if rubric_submission.rubric_version == Some(rubric.version) {
+                    continue;
+                }
+
+                let mut graded_data = rubric.data.clone();
+                graded_data.carry_picks_from(&rubric_submission.graded_data);
+                rubric_submission.graded_data = graded_data;
+                rubric_submission.rubric_version = Some(rubric.version);
+                RubricSubmission::upsert(conn, rubric_submission)?;
+                count += 1;
+            }
+        }
+        Ok(count)
+    })
+}
+
+pub fn export_rubric_data(
+    data: &RubricTableData,
+    format: RubricFileFormat,
+) -> Result<String, IkigaiError> {
+    match format {
+        RubricFileFormat::Csv => rubric_data_to_csv(data),
+        RubricFileFormat::Json => Ok(serde_json::to_string_pretty(data)?),
+    }
+}
+
+// Csv file does not keep rubric type, so it is given by caller
+pub fn import_rubric_data(
+    content: &str,
+    format: RubricFileFormat,
+    rubric_type: RubricType,
+) -> Result<RubricTableData, IkigaiError> {
+    let data = match format {
+        RubricFileFormat::Csv => rubric_data_from_csv(content, rubric_type)?,
+        RubricFileFormat::Json => serde_json::from_str(content)
+            .map_err(|e| IkigaiError::new_bad_request(format!("Invalid rubric JSON: {e}")))?,
+    };
+    data.validate_structure()
+        .map_err(IkigaiError::new_bad_request)?;
+    Ok(data)
+}
+
+// One row per criteria: name, weighting, then explanation, score and to score of each level
+fn rubric_data_to_csv(data: &RubricTableData) -> Result<String, IkigaiError> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    let mut headers = vec!["Criteria".to_string(), "Weighting".to_string()];
+    for level in &data.level {
+        headers.push(level.clone());
+        headers.push(format!("{level} Score"));
+        headers.push(format!("{level} To Score"));
+    }
+    writer.write_record(&headers)?;
+
+    for (index, criteria) in data.criteria.iter().enumerate() {
+        let weighting = data
+            .weighting_criteria
+            .get(index)
+            .copied()
+            .flatten()
+            .map(|weighting| weighting.to_string())
+            .unwrap_or_default();
+        let mut record = vec![criteria.clone(), weighting];
+        for item in data.items.get(index).into_iter().flatten() {
+            record.push(item.explanation.clone());
+            record.push(item.score.to_string());
+            if data.rubric_type == RubricType::PointRange {
+                record.push(item.to_score.to_string());
+            } else {
+                record.push(String::new());
+            }
+        }
+        writer.write_record(&record)?;
+    }
+
+    csv_writer_into_string(writer)
+}
+
+fn rubric_data_from_csv(
+    content: &str,
+    rubric_type: RubricType,
+) -> Result<RubricTableData, IkigaiError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(content.as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|e| IkigaiError::new_bad_request(format!("Invalid rubric CSV: {e}")))?
+        .clone();
+    if headers.len() < 5 || (headers.len() - 2) % 3 != 0 {
+        return Err(IkigaiError::new_bad_request(
+            "Rubric CSV needs criteria, weighting and 3 columns for each level",
+        ));
+    }
+    let level: Vec<String> = headers
+        .iter()
+        .skip(2)
+        .step_by(3)
+        .map(|name| name.trim().to_string())
+        .collect();
+
+    let mut data = RubricTableData {
+        rubric_type,
+        level,
+        ..Default::default()
+    };
+    for (row_index, record) in reader.records().enumerate() {
+        let record =
+            record.map_err(|e| IkigaiError::new_bad_request(format!("Invalid rubric CSV: {e}")))?;
+        let row_number = row_index + 2;
+        let fields: Vec<&str> = record.iter().map(|field| field.trim()).collect();
+        if fields.len() != headers.len() {
+            return Err(IkigaiError::new_bad_request(format!(
+                "Row {row_number} has {} columns, expected {}",
+                fields.len(),
+                headers.len()
+            )));
+        }
+
+        data.criteria.push(fields[0].to_string());
+        data.weighting_criteria
+            .push(parse_optional_number(fields[1], row_number)?);
+        let items = fields[2..]
+            .chunks(3)
+            .map(|cells| {
+                Ok(RubricTableItem {
+                    explanation: cells[0].to_string(),
+                    score: parse_optional_number(cells[1], row_number)?.unwrap_or_default(),
+                    to_score: parse_optional_number(cells[2], row_number)?.unwrap_or_default(),
+                    ..Default::default()
+                })
+            })
+            .collect::<Result<Vec<RubricTableItem>, IkigaiError>>()?;
+        data.items.push(items);
+    }
+
+    if data
+        .weighting_criteria
+        .iter()
+        .all(|weighting| weighting.is_none())
+    {
+        data.weighting_criteria = vec![];
+    }
+    Ok(data)
+}
+
+fn parse_optional_number(value: &str, row_number: usize) -> Result<Option<f64>, IkigaiError> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    value.parse::<f64>().map(Some).map_err(|_| {
+        IkigaiError::new_bad_request(format!("Row {row_number} has invalid number \"{value}\""))
+    })
+}
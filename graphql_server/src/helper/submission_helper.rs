@@ -1,10 +1,14 @@
 use actix::SystemService;
-use diesel::PgConnection;
+use diesel::{Connection, PgConnection};
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 use crate::db::*;
 use crate::error::IkigaiError;
 use crate::graphql::notification_center::{NotificationCenter, SubmitCompleted};
+use crate::helper::apply_peer_review_score;
+use crate::notification_center::send_notification;
+use crate::util::get_now_as_secs;
 
 pub fn submit_submission(
     conn: &mut PgConnection,
@@ -12,17 +16,40 @@ pub fn submit_submission(
     assignment: &Assignment,
     notify_student: bool,
 ) -> Result<(), IkigaiError> {
-    let grade = auto_grade(conn, submission.document_id, submission.user_id)?;
+    let grade = auto_grade(conn, submission)?;
 
     let mut final_grade = grade;
     if let Some(band_score_id) = assignment.band_score_id {
         let band_score = BandScore::find(conn, band_score_id)?;
-        final_grade = band_score.find_score(grade);
+        final_grade = band_score.find_score(grade, max_auto_grade(conn, assignment)?);
     }
 
-    // Auto release grade in case teacher choose auto grade
+    let submit_at = get_now_as_secs();
+    let late_by = assignment
+        .due_at
+        .map_or(0, |due_at| (submit_at - due_at).max(0));
+    final_grade = assignment.apply_late_penalty(final_grade, late_by);
+
+    // Auto release grade in case teacher choose auto grade, unless grades are held until release
     let is_auto_grade = assignment.grade_method == GradeMethod::Auto;
-    Submission::submit(conn, submission.id, grade, final_grade, is_auto_grade)?;
+    let hold_grade = is_auto_grade && assignment.is_holding_grades();
+    let submitted_submission = Submission::submit(
+        conn,
+        submission.id,
+        grade,
+        final_grade,
+        is_auto_grade && !hold_grade,
+        hold_grade,
+        submit_at,
+        late_by,
+    )?;
+    SubmissionGradeHistory::record(
+        conn,
+        submission,
+        &submitted_submission,
+        None,
+        GradeChangeSource::Submit,
+    )?;
     if notify_student {
         NotificationCenter::from_registry().do_send(SubmitCompleted {
             user_id: submission.user_id,
@@ -33,6 +60,292 @@ pub fn submit_submission(
     Ok(())
 }
 
+pub fn check_assignment_availability(assignment: &Assignment) -> Result<(), IkigaiError> {
+    let now = get_now_as_secs();
+    if assignment.open_at.is_some_and(|open_at| now < open_at) {
+        return Err(IkigaiError::new_bad_request(
+            "This assignment is not open yet!",
+        ));
+    }
+
+    if assignment.close_at.is_some_and(|close_at| now > close_at) {
+        return Err(IkigaiError::new_bad_request(
+            "This assignment is already closed!",
+        ));
+    }
+
+    Ok(())
+}
+
+// Student is notified now, or when grades are released if assignment holds grades
+pub fn grade_submission(
+    conn: &mut PgConnection,
+    submission: &Submission,
+    assignment: &Assignment,
+    grade_data: GradeSubmissionData,
+    actor_id: i32,
+    source: GradeChangeSource,
+) -> Result<Submission, IkigaiError> {
+    let graded_submission = conn.transaction::<_, IkigaiError, _>(|conn| {
+        apply_grade_submission(conn, submission, assignment, grade_data, actor_id, source)
+    })?;
+
+    if !graded_submission.grade_held {
+        send_feedback_notifications(conn, std::slice::from_ref(&graded_submission))?;
+    }
+    Ok(graded_submission)
+}
+
+// All submissions are graded in one transaction, then each student gets one notification
+pub fn bulk_grade_submissions(
+    conn: &mut PgConnection,
+    submissions: &[Submission],
+    assignments: &HashMap<i32, Assignment>,
+    grade_data: GradeSubmissionData,
+    actor_id: i32,
+) -> Result<Vec<Submission>, IkigaiError> {
+    let graded_submissions = conn.transaction::<_, IkigaiError, _>(|conn| {
+        let mut graded_submissions = vec![];
+        for submission in submissions {
+            let assignment = assignments
+                .get(&submission.assignment_id)
+                .ok_or(IkigaiError::new_bad_request("Assignment not found"))?;
+            graded_submissions.push(apply_grade_submission(
+                conn,
+                submission,
+                assignment,
+                grade_data.clone(),
+                actor_id,
+                GradeChangeSource::Grade,
+            )?);
+        }
+        Ok(graded_submissions)
+    })?;
+
+    let released_submissions: Vec<Submission> = graded_submissions
+        .iter()
+        .filter(|submission| !submission.grade_held)
+        .cloned()
+        .collect();
+    send_feedback_notifications(conn, &released_submissions)?;
+    Ok(graded_submissions)
+}
+
+fn apply_grade_submission(
+    conn: &mut PgConnection,
+    submission: &Submission,
+    assignment: &Assignment,
+    mut grade_data: GradeSubmissionData,
+    actor_id: i32,
+    source: GradeChangeSource,
+) -> Result<Submission, IkigaiError> {
+    // Grade of double marked submission is only set by moderation
+    if assignment.double_marking && source != GradeChangeSource::Moderation {
+        return Err(IkigaiError::new_bad_request(
+            "Assignment is double marked, please add a marking instead",
+        ));
+    }
+
+    if let Some(final_grade) = grade_data.final_grade {
+        let final_grade = apply_peer_review_score(conn, submission, assignment, final_grade)?;
+        grade_data.final_grade =
+            Some(assignment.apply_late_penalty(final_grade, submission.late_by));
+    }
+    let graded_submission = Submission::grade_submission(
+        conn,
+        submission.id,
+        grade_data,
+        assignment.is_holding_grades(),
+    )?;
+    SubmissionGradeHistory::record(conn, submission, &graded_submission, Some(actor_id), source)?;
+    Ok(graded_submission)
+}
+
+pub fn release_grades(
+    conn: &mut PgConnection,
+    assignment_id: i32,
+) -> Result<Vec<Submission>, IkigaiError> {
+    let submissions = Submission::release_held_grades(conn, assignment_id)?;
+    Assignment::mark_grades_released(conn, assignment_id)?;
+    send_feedback_notifications(conn, &submissions)?;
+    Ok(submissions)
+}
+
+// Group feedback by student, a student having many graded submissions receives one notification
+pub fn send_feedback_notifications(
+    conn: &mut PgConnection,
+    submissions: &[Submission],
+) -> Result<(), IkigaiError> {
+    let document_ids = submissions
+        .iter()
+        .map(|submission| submission.document_id)
+        .collect();
+    let titles: HashMap<Uuid, String> = Document::find_by_ids(conn, document_ids)?
+        .into_iter()
+        .map(|document| (document.id, document.title))
+        .collect();
+
+    let mut contexts_by_user: HashMap<i32, Vec<FeedbackSubmissionContext>> = HashMap::new();
+    for submission in submissions {
+        contexts_by_user
+            .entry(submission.user_id)
+            .or_default()
+            .push(FeedbackSubmissionContext {
+                document_submission_id: submission.document_id,
+                submission_name: titles
+                    .get(&submission.document_id)
+                    .cloned()
+                    .unwrap_or_default(),
+            });
+    }
+
+    for (user_id, mut contexts) in contexts_by_user {
+        let notification = if contexts.len() == 1 {
+            Notification::new_feedback_submission_notification(contexts.remove(0))
+        } else {
+            Notification::new_feedback_submissions_notification(FeedbackSubmissionsContext {
+                submissions: contexts,
+            })
+        };
+        let notification = Notification::insert(conn, notification)?;
+        send_notification(conn, notification, vec![user_id])?;
+    }
+
+    Ok(())
+}
+
+pub fn request_regrade(
+    conn: &mut PgConnection,
+    submission: &Submission,
+    assignment: &Assignment,
+    student: &User,
+    reason: String,
+    criterion: Option<String>,
+) -> Result<RegradeRequest, IkigaiError> {
+    if !submission.is_grade_released() {
+        return Err(IkigaiError::new_bad_request("Submission is not graded yet"));
+    }
+    if reason.trim().is_empty() {
+        return Err(IkigaiError::new_bad_request("Reason cannot be empty"));
+    }
+    let requests = RegradeRequest::find_all_by_submission(conn, submission.id)?;
+    if requests
+        .iter()
+        .any(|request| request.status == RegradeRequestStatus::Pending)
+    {
+        return Err(IkigaiError::new_bad_request(
+            "Submission already has a pending regrade request",
+        ));
+    }
+    if let Some(criterion) = &criterion {
+        let is_valid_criterion = RubricSubmission::find_by_submission_opt(conn, submission.id)?
+            .is_some_and(|item| item.graded_data.criteria.contains(criterion));
+        if !is_valid_criterion {
+            return Err(IkigaiError::new_bad_request(
+                "Criterion is not in rubric of submission",
+            ));
+        }
+    }
+
+    let request = RegradeRequest::insert(
+        conn,
+        NewRegradeRequest::new(submission.id, student.id, reason, criterion),
+    )?;
+
+    let assignment_document = Document::find_by_id(conn, assignment.document_id)?;
+    let notification = Notification::new_regrade_request_notification(RegradeRequestContext {
+        document_submission_id: submission.document_id,
+        submission_name: assignment_document.title,
+        // Submission is graded anonymously again once the request is accepted
+        student_name: if assignment.anonymous_grading {
+            submission.pseudonym()
+        } else {
+            student.name()
+        },
+        reason: request.reason.clone(),
+    });
+    let notification = Notification::insert(conn, notification)?;
+    let receivers = SpaceMember::find_all_space_members_by_role_and_class(
+        conn,
+        assignment_document.space_id.unwrap_or(-1),
+        Role::Teacher,
+    )?
+    .iter()
+    .map(|space_member| space_member.user_id)
+    .collect();
+    send_notification(conn, notification, receivers)?;
+
+    Ok(request)
+}
+
+// Annotation must be anchored to a valid text range in content of submission document
+pub fn check_annotation_anchor(
+    conn: &mut PgConnection,
+    submission: &Submission,
+    annotation: &FeedbackAnnotation,
+) -> Result<(), IkigaiError> {
+    if annotation.from_offset < 0 || annotation.from_offset >= annotation.to_offset {
+        return Err(IkigaiError::new_bad_request("Invalid annotated text range"));
+    }
+    if annotation.comment.trim().is_empty() {
+        return Err(IkigaiError::new_bad_request("Comment cannot be empty"));
+    }
+
+    let page_content = PageContent::find(conn, annotation.page_content_id)?;
+    let page = Page::find(conn, page_content.page_id)?;
+    if page.document_id != submission.document_id {
+        return Err(IkigaiError::new_bad_request(
+            "Annotated content is not in submission",
+        ));
+    }
+    if let Some(writing_block_id) = annotation.writing_block_id {
+        let writing_block = WritingBlock::find(conn, writing_block_id)?;
+        if writing_block.page_content_id != page_content.id {
+            return Err(IkigaiError::new_bad_request(
+                "Annotated writing block is not in page content",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn check_file_comment(
+    conn: &mut PgConnection,
+    submission: &Submission,
+    file_comment: &FileComment,
+) -> Result<(), IkigaiError> {
+    if file_comment.start_time < 0.0
+        || file_comment
+            .end_time
+            .is_some_and(|end_time| end_time <= file_comment.start_time)
+    {
+        return Err(IkigaiError::new_bad_request(
+            "Invalid time range of comment",
+        ));
+    }
+    if file_comment.comment.trim().is_empty() {
+        return Err(IkigaiError::new_bad_request("Comment cannot be empty"));
+    }
+
+    let file = File::find_by_id(conn, file_comment.file_id)?;
+    if !file.content_type.starts_with("audio/") {
+        return Err(IkigaiError::new_bad_request("File is not an audio"));
+    }
+    let page_ids = Page::find_all_by_document_id(conn, submission.document_id)?
+        .into_iter()
+        .map(|page| page.id)
+        .collect();
+    let is_in_submission = PageContent::find_all_by_pages(conn, page_ids)?
+        .iter()
+        .any(|page_content| page_content.get_json_content().has_file_handler(file.uuid));
+    if !is_in_submission {
+        return Err(IkigaiError::new_bad_request("File is not in submission"));
+    }
+
+    Ok(())
+}
+
 pub fn try_add_rubric_submission(
     conn: &mut PgConnection,
     assignment: &Assignment,
@@ -41,19 +354,86 @@ pub fn try_add_rubric_submission(
     if let Some(rubric_id) = assignment.grade_by_rubric_id {
         if assignment.grade_method == GradeMethod::Rubric {
             let rubric = Rubric::find_by_id(conn, rubric_id)?;
-            let rubric_submission = RubricSubmission::new(submission.id, rubric.id, rubric.data);
+            let rubric_submission = RubricSubmission::new(submission.id, &rubric);
             RubricSubmission::upsert(conn, rubric_submission)?;
         }
     }
     Ok(())
 }
 
-// FIXME: Calculate new method
-// Only count quiz scores and ignore nested, page block, etc
-pub fn auto_grade(
-    _conn: &PgConnection,
-    _document_id: Uuid,
-    _user_id: i32,
+// Only count quiz blocks of the document itself, ignore child documents.
+// A quiz block is graded only if it is cloned from a quiz block of assignment document,
+// each original is counted once. Type, score and answer key come from the original.
+pub fn auto_grade(conn: &mut PgConnection, submission: &Submission) -> Result<f64, IkigaiError> {
+    let assignment = Assignment::find_by_id(conn, submission.assignment_id)?;
+    let original_blocks: HashMap<Uuid, QuizBlock> =
+        find_all_quiz_blocks_by_document(conn, assignment.document_id)?
+            .into_iter()
+            .map(|block| (block.id, block))
+            .collect();
+    let original_ids: Vec<Uuid> = original_blocks.keys().copied().collect();
+    let answer_keys: HashMap<Uuid, QuizAnswer> =
+        QuizAnswerKey::find_all_by_quiz_blocks(conn, &original_ids)?
+            .into_iter()
+            .map(|answer_key| (answer_key.quiz_block_id, answer_key.answer))
+            .collect();
+
+    let mut graded_ids = HashSet::new();
+    let mut grade = 0.0;
+    for block in find_all_quiz_blocks_by_document(conn, submission.document_id)? {
+        let source_block = block
+            .original_quiz_block_id
+            .and_then(|id| original_blocks.get(&id));
+        if let Some(source_block) = source_block {
+            if !graded_ids.insert(source_block.id) {
+                continue;
+            }
+
+            let answer_key = answer_keys.get(&source_block.id);
+            if let (Some(answer), Some(answer_key)) = (&block.answer, answer_key) {
+                grade += answer.grade(answer_key, source_block.quiz_type, source_block.score);
+            }
+        }
+    }
+
+    Ok(grade)
+}
+
+// Total score of quiz blocks of assignment document
+pub fn max_auto_grade(
+    conn: &mut PgConnection,
+    assignment: &Assignment,
 ) -> Result<f64, IkigaiError> {
-    Ok(0.0)
+    let quiz_blocks = find_all_quiz_blocks_by_document(conn, assignment.document_id)?;
+    Ok(quiz_blocks.iter().map(|block| block.score).sum())
+}
+
+// Quiz blocks removed from page content body are ignored
+pub fn find_all_quiz_blocks_by_document(
+    conn: &mut PgConnection,
+    document_id: Uuid,
+) -> Result<Vec<QuizBlock>, IkigaiError> {
+    let page_ids = Page::find_all_by_document_id(conn, document_id)?
+        .into_iter()
+        .map(|page| page.id)
+        .collect();
+    let page_contents = PageContent::find_all_by_pages(conn, page_ids)?;
+    let block_ids: Vec<Uuid> = page_contents
+        .iter()
+        .flat_map(|page_content| {
+            page_content
+                .get_json_content()
+                .find_block_ids("quizBlock", "quizBlockId")
+        })
+        .collect();
+    let page_content_ids: Vec<Uuid> = page_contents
+        .iter()
+        .map(|page_content| page_content.id)
+        .collect();
+    let quiz_blocks = QuizBlock::find_all_by_page_contents(conn, &page_content_ids)?
+        .into_iter()
+        .filter(|block| block_ids.contains(&block.id))
+        .collect();
+
+    Ok(quiz_blocks)
 }
@@ -29,6 +29,7 @@ use crate::authentication_token::{ActiveSpaceId, JwtToken};
 use crate::background_job::register_jobs;
 use crate::graphql::context_caching_data::RequestContextCachingData;
 use crate::graphql::{build_schema, IkigaiSchema};
+use crate::helper::init_band_score_presets;
 use crate::util::log_util;
 
 mod authentication_token;
@@ -138,6 +139,9 @@ async fn main() -> std::io::Result<()> {
     dotenv().ok();
     log_util::setup_logger().unwrap();
     register_jobs();
+    if let Err(reason) = init_band_score_presets().await {
+        error!("Cannot init band score presets: {:?}", reason);
+    }
 
     let addr = format!("127.0.0.1:{}", std::env::var("PORT").unwrap());
     info!("Graphql Server will run at {addr}");
@@ -51,6 +51,18 @@ pub fn parse_context(notification: &Notification) -> Option<Box<dyn ContextMessa
                     .ok()?;
             Some(Box::new(value))
         }
+        NotificationType::FeedbackSubmissions => {
+            let value =
+                serde_json::from_value::<FeedbackSubmissionsContext>(notification.context.clone())
+                    .ok()?;
+            Some(Box::new(value))
+        }
+        NotificationType::RegradeRequest => {
+            let value =
+                serde_json::from_value::<RegradeRequestContext>(notification.context.clone())
+                    .ok()?;
+            Some(Box::new(value))
+        }
     }
 }
 